@@ -24,7 +24,7 @@
 use cortex_m_rt::{entry, exception, ExceptionFrame};
 use embedded_graphics::{image::Image, pixelcolor::Rgb565, prelude::*};
 use panic_halt as _;
-use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
+use ssd1306::{eh02::I2CDisplayInterface, prelude::*, Ssd1306};
 use stm32f1xx_hal::{
     i2c::{BlockingI2c, DutyCycle, Mode},
     prelude::*,
@@ -65,8 +65,9 @@ fn main() -> ! {
 
     let interface = I2CDisplayInterface::new(i2c);
     let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
-        .into_buffered_graphics_mode();
-    display.init().unwrap();
+        .into_buffered_graphics_mode()
+        .init()
+        .unwrap();
 
     let bmp = Bmp::from_slice(include_bytes!("./rust.bmp")).expect("Failed to load BMP image");
 
@@ -24,7 +24,7 @@ use embedded_graphics::{
     primitives::{Circle, PrimitiveStyleBuilder, Rectangle, Triangle},
 };
 use panic_halt as _;
-use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
+use ssd1306::{eh02::I2CDisplayInterface, prelude::*, Ssd1306};
 use stm32f1xx_hal::{
     i2c::{BlockingI2c, DutyCycle, Mode},
     prelude::*,
@@ -64,8 +64,9 @@ fn main() -> ! {
 
     let interface = I2CDisplayInterface::new(i2c);
     let mut display = Ssd1306::new(interface, DisplaySize72x40, DisplayRotation::Rotate0)
-        .into_buffered_graphics_mode();
-    display.init().unwrap();
+        .into_buffered_graphics_mode()
+        .init()
+        .unwrap();
 
     let size = 10;
     let offset = Point::new(10, (42 / 2) - (size / 2) - 1);
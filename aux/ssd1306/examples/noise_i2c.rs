@@ -22,7 +22,7 @@
 use cortex_m_rt::{entry, exception, ExceptionFrame};
 use panic_halt as _;
 use rand::prelude::*;
-use ssd1306::{prelude::*, I2CDisplayInterface, Ssd1306};
+use ssd1306::{eh02::I2CDisplayInterface, prelude::*, Ssd1306};
 use stm32f1xx_hal::{
     i2c::{BlockingI2c, DutyCycle, Mode},
     prelude::*,
@@ -61,8 +61,9 @@ fn main() -> ! {
     );
 
     let interface = I2CDisplayInterface::new(i2c);
-    let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
-    display.init().unwrap();
+    let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+        .init()
+        .unwrap();
 
     let mut buf = [0x00u8; 1024];
 
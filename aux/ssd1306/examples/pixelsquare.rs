@@ -68,12 +68,12 @@ fn main() -> ! {
         clocks,
     );
 
-    let interface = display_interface_spi::SPIInterfaceNoCS::new(spi, dc);
+    let interface = ssd1306::eh02::SPIInterfaceNoCS::new(spi, dc);
     let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
         .into_buffered_graphics_mode();
 
-    display.reset(&mut rst, &mut delay).unwrap();
-    display.init().unwrap();
+    display.reset_eh02(&mut rst, &mut delay).unwrap();
+    let mut display = display.init().unwrap();
 
     // Top side
     display.set_pixel(0, 0, true);
@@ -9,7 +9,7 @@
 
 #[rtic::app(device = stm32f1xx_hal::pac, peripherals = true, dispatchers = [EXTI0])]
 mod app {
-    use display_interface_spi::SPIInterfaceNoCS;
+    use ssd1306::eh02::SPIInterfaceNoCS;
     use embedded_graphics::{
         geometry::Point,
         image::Image,
@@ -100,12 +100,12 @@ mod app {
             clocks,
         );
 
-        let interface = display_interface_spi::SPIInterfaceNoCS::new(spi, dc);
+        let interface = ssd1306::eh02::SPIInterfaceNoCS::new(spi, dc);
         let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate180)
             .into_buffered_graphics_mode();
 
-        display.reset(&mut rst, &mut delay).unwrap();
-        display.init().unwrap();
+        display.reset_eh02(&mut rst, &mut delay).unwrap();
+        let mut display = display.init().unwrap();
 
         // Update framerate
         let mut timer = dp.TIM1.counter_ms(&clocks);
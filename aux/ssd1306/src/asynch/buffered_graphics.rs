@@ -0,0 +1,284 @@
+//! Async buffered graphics mode.
+
+use super::{AsyncWriteOnlyDataCommand, DisplayConfigAsync, Ssd1306Async};
+use crate::{
+    framebuffer::DirtyArea,
+    mode::{BufferedGraphicsMode, ShadowMode},
+    power::PowerState,
+    rotation::{DisplayRotation, Orientation},
+    size::DisplaySize,
+};
+use display_interface::{DataFormat::U8, DisplayError};
+
+impl<DI, SIZE, SHADOW> DisplayConfigAsync
+    for Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    type Error = DisplayError;
+
+    /// Set the display rotation
+    ///
+    /// This method resets the cursor but does not clear the screen.
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+        Ok(self.set_rotation(rot).await?)
+    }
+
+    /// Set the display orientation
+    ///
+    /// This method does not clear the screen.
+    async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation).await?)
+    }
+
+    /// Initialise and clear the display in graphics mode.
+    async fn init(&mut self) -> Result<(), DisplayError> {
+        self.display.mode.invalidate_shadow();
+        self.clear_buffer();
+        let mode = self.display.controller.area_addr_mode();
+        self.init_with_addr_mode(mode).await
+    }
+}
+
+impl<DI, SIZE, SHADOW> Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    /// Clear the underlying framebuffer. You need to call `disp.flush()` for any effect on the screen.
+    pub fn clear_buffer(&mut self) {
+        self.display.mode.framebuffer_mut().fill(false);
+    }
+
+    /// Write out data to a display.
+    ///
+    /// This only updates the parts of the display that have changed since the last flush, with
+    /// each page keeping track of its own changed columns. In [`Shadowed`](crate::mode::Shadowed)
+    /// mode only the bytes that differ from what was last sent are updated. Nothing is sent while
+    /// the display is [asleep](crate::power::PowerState::Asleep). The same bytes are sent as by
+    /// [`Ssd1306::flush`](crate::Ssd1306::flush).
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306, Ssd1306Async};
+    ///
+    /// let pixels = [(0, 0), (127, 63), (5, 20), (6, 21), (100, 40)];
+    /// let (mut blocking, mut log) = (CommandLog::new(), CommandLog::new());
+    ///
+    /// let mut display = Ssd1306::new(&mut blocking, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.flush().unwrap();
+    /// for (x, y) in pixels {
+    ///     display.set_pixel(x, y, true);
+    /// }
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// embassy_futures::block_on(async {
+    ///     let mut display = Ssd1306Async::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///         .into_buffered_graphics_mode()
+    ///         .init()
+    ///         .await
+    ///         .unwrap();
+    ///     // `init` clears the buffer, so this sends all of it
+    ///     display.flush().await.unwrap();
+    ///     for (x, y) in pixels {
+    ///         display.set_pixel(x, y, true);
+    ///     }
+    ///     display.flush().await.unwrap();
+    /// });
+    ///
+    /// assert_eq!(log, blocking);
+    /// // Only the changed columns of each page are sent the second time
+    /// assert_eq!(log.data_len(), 1024 + 5);
+    /// ```
+    pub async fn flush(&mut self) -> Result<(), DisplayError> {
+        if self.display.power == PowerState::Asleep {
+            return Ok(());
+        }
+
+        let result = self.flush_pages().await;
+        if result.is_err() {
+            self.display.mode.invalidate_shadow();
+        }
+
+        result
+    }
+
+    async fn flush_pages(&mut self) -> Result<(), DisplayError> {
+        while let Some((area, page)) = self.display.next_flush_page() {
+            self.send_area_page(area, page).await?;
+            self.display.mode.page_sent();
+        }
+
+        Ok(())
+    }
+
+    /// Send one page of an area.
+    async fn send_area_page(&mut self, area: DirtyArea, page: u8) -> Result<(), DisplayError> {
+        let (commands, data) = self.display.area_page(
+            self.display.mode.framebuffer().buffer().as_ref(),
+            &area,
+            page,
+        )?;
+        commands.send_async(&mut self.display.interface).await?;
+        self.display.interface.send_data(U8(data)).await?;
+        self.display.mode.shadow_page(&area, page);
+
+        Ok(())
+    }
+
+    /// Send a rectangle of the buffer to the display, whether it has changed or not.
+    ///
+    /// The rectangle is given in display coordinates and clipped to the display. Changes outside
+    /// of it are left for the next [`flush`](Ssd1306Async::flush). Nothing is sent while the
+    /// display is [asleep](crate::power::PowerState::Asleep).
+    #[cfg(feature = "graphics")]
+    pub async fn flush_area(&mut self, area: &Rectangle) -> Result<(), DisplayError> {
+        if self.display.power == PowerState::Asleep {
+            return Ok(());
+        }
+
+        let display = &mut self.display;
+        let rect = match display.mode.framebuffer().clip(area) {
+            Some(rect) => rect,
+            None => return Ok(()),
+        };
+        let area = match display
+            .mode
+            .take_area(&display.size, display.controller, rect)
+        {
+            Some(area) => area,
+            None => return Ok(()),
+        };
+
+        for page in area.pages() {
+            if let Err(error) = self.send_area_page(area, page).await {
+                self.display.mode.invalidate_shadow();
+                return Err(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send the whole buffer to the display, whether it has changed or not.
+    ///
+    /// This repaints the display after its RAM was lost or corrupted, for example by a bus
+    /// glitch or after the panel was reset or plugged back in.
+    pub async fn flush_all(&mut self) -> Result<(), DisplayError> {
+        self.display.mode.mark_all();
+        self.flush().await
+    }
+
+    /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. If the X and Y
+    /// coordinates are out of the bounds of the display, this method call is a noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
+        self.display.mode.framebuffer_mut().set_pixel(x, y, value);
+    }
+
+    /// Whether a pixel in the buffer is on, or `None` if the coordinates are outside the display.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<bool> {
+        self.display.mode.framebuffer().get_pixel(x, y)
+    }
+
+    /// The page-packed pixel buffer. See [`Framebuffer::buffer`](crate::Framebuffer::buffer) for
+    /// its layout.
+    pub fn buffer(&self) -> &SIZE::Buffer {
+        self.display.mode.framebuffer().buffer()
+    }
+
+    /// The page-packed pixel buffer for writing. The whole display is marked as changed.
+    pub fn buffer_mut(&mut self) -> &mut SIZE::Buffer {
+        self.display.mode.framebuffer_mut().buffer_mut()
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE, SHADOW> DrawTarget for Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    type Color = BinaryColor;
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.display
+            .mode
+            .framebuffer_mut()
+            .draw_iter(pixels)
+            .map_err(|never| match never {})
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.display
+            .mode
+            .framebuffer_mut()
+            .fill_solid(area, color)
+            .map_err(|never| match never {})
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.display
+            .mode
+            .framebuffer_mut()
+            .fill_contiguous(area, colors)
+            .map_err(|never| match never {})
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.display.mode.framebuffer_mut().fill(color.is_on());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE, SHADOW> OriginDimensions
+    for Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE, SHADOW> GetPixel for Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<BinaryColor> {
+        self.display.mode.framebuffer().pixel(p)
+    }
+}
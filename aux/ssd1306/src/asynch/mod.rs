@@ -0,0 +1,588 @@
+//! Async display driver.
+//!
+//! Available with the `async` feature. [`Ssd1306Async`] offers the same functionality as
+//! [`Ssd1306`](crate::Ssd1306), but sends commands and data through an
+//! [`AsyncWriteOnlyDataCommand`] interface so other tasks can run while a frame is being
+//! transferred. It shares the command encoding and display modes with the blocking driver.
+//!
+//! The [`I2CInterface`](crate::prelude::I2CInterface) and
+//! [`SPIInterface`](crate::prelude::SPIInterface) types implement the async interface when they
+//! wrap an [`embedded-hal-async`](https://docs.rs/embedded-hal-async) bus.
+//!
+//! ```rust
+//! # use ssd1306::test_helpers::AsyncI2cStub;
+//! # let i2c = AsyncI2cStub;
+//! use embedded_graphics::{
+//!     pixelcolor::BinaryColor,
+//!     prelude::*,
+//!     primitives::{PrimitiveStyle, Rectangle},
+//! };
+//! use ssd1306::{asynch::DisplayConfigAsync, prelude::*, I2CDisplayInterface, Ssd1306Async};
+//!
+//! # embassy_futures::block_on(async {
+//! let interface = I2CDisplayInterface::new(i2c);
+//! let mut display = Ssd1306Async::new(
+//!     interface,
+//!     DisplaySize128x64,
+//!     DisplayRotation::Rotate0,
+//! ).into_buffered_graphics_mode()
+//! .init().await
+//! .unwrap();
+//!
+//! Rectangle::new(Point::new(8, 8), Size::new(16, 16))
+//!     .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+//!     .draw(&mut display)
+//!     .unwrap();
+//!
+//! display.flush().await.unwrap();
+//! # });
+//! ```
+
+mod buffered_graphics;
+mod terminal;
+
+pub use display_interface::AsyncWriteOnlyDataCommand;
+
+use crate::{
+    brightness::Brightness,
+    command::{AddrMode, Command},
+    config::Ssd1306Config,
+    controller::Controller,
+    framebuffer::DirtyArea,
+    mode::{
+        BasicMode, BufferedGraphicsMode, InitializedMode, Shadowed, TerminalMode, Uninitialized,
+    },
+    power::{PowerState, WAKE_DELAY_MS},
+    rotation::{DisplayRotation, Orientation},
+    size::DisplaySize,
+    Error, Framebuffer, Ssd1306,
+};
+use display_interface::{DataFormat::U8, DisplayError};
+use embedded_hal_async::delay::DelayNs;
+
+/// Common functions to all async display modes.
+#[allow(async_fn_in_trait)]
+pub trait DisplayConfigAsync {
+    /// Error.
+    type Error;
+
+    /// Set display rotation.
+    async fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Self::Error>;
+
+    /// Set display orientation.
+    async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Self::Error>;
+
+    /// Initialise and configure the display for the given mode.
+    ///
+    /// Displays are initialised by [`Ssd1306Async::init`], so this is only needed to initialise
+    /// the display again.
+    async fn init(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Async SSD1306 driver.
+///
+/// It wraps an [`Ssd1306`] and picks the commands to send the same way, so both drivers send
+/// the same bytes for the same calls. Note that some methods are only available when the
+/// display is configured in a certain [`mode`](crate::mode).
+#[derive(Copy, Clone, Debug)]
+pub struct Ssd1306Async<DI, SIZE, MODE> {
+    display: Ssd1306<DI, SIZE, MODE>,
+}
+
+impl<DI, SIZE> Ssd1306Async<DI, SIZE, Uninitialized<BasicMode>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Create a basic async SSD1306 interface.
+    ///
+    /// Use the `into_*_mode` methods to enable more functionality, then
+    /// [`init`](Ssd1306Async::init) the display to use it.
+    pub fn new(interface: DI, size: SIZE, rotation: DisplayRotation) -> Self {
+        Self {
+            display: Ssd1306::new(interface, size, rotation),
+        }
+    }
+}
+
+impl<DI, SIZE> Ssd1306Async<DI, SIZE, BasicMode>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Clear the display. See [`Ssd1306::clear`](crate::Ssd1306::clear).
+    ///
+    /// ```rust
+    /// use ssd1306::{controller::Controller, prelude::*, test_helpers::CommandLog};
+    /// use ssd1306::{Ssd1306, Ssd1306Async};
+    ///
+    /// for controller in [Controller::Ssd1306, Controller::Sh1106] {
+    ///     let (mut blocking, mut log) = (CommandLog::new(), CommandLog::new());
+    ///
+    ///     Ssd1306::new(&mut blocking, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///         .with_controller(controller)
+    ///         .init()
+    ///         .unwrap()
+    ///         .clear()
+    ///         .unwrap();
+    ///
+    ///     embassy_futures::block_on(async {
+    ///         let mut display =
+    ///             Ssd1306Async::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///                 .with_controller(controller)
+    ///                 .init()
+    ///                 .await
+    ///                 .unwrap();
+    ///         display.clear().await.unwrap();
+    ///     });
+    ///
+    ///     assert_eq!(log, blocking);
+    ///     // All of display RAM is cleared
+    ///     let ram = usize::from(controller.ram_columns()) * 8;
+    ///     assert_eq!(log.data_len(), ram);
+    /// }
+    /// ```
+    pub async fn clear(&mut self) -> Result<(), DisplayError> {
+        let end = (self.display.controller.ram_columns(), SIZE::DRIVER_ROWS);
+        self.clear_area((0, 0), end).await?;
+
+        Ok(())
+    }
+}
+
+impl<DI, SIZE> DisplayConfigAsync for Ssd1306Async<DI, SIZE, BasicMode>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Error = DisplayError;
+
+    /// Set the display rotation.
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+        Ok(self.set_rotation(rot).await?)
+    }
+
+    /// Set the display orientation.
+    async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation).await?)
+    }
+
+    /// Initialise in horizontal addressing mode, or page addressing mode if the controller
+    /// doesn't support it.
+    async fn init(&mut self) -> Result<(), DisplayError> {
+        let mode = self.display.controller.area_addr_mode();
+        self.init_with_addr_mode(mode).await
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1306Async<DI, SIZE, MODE>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Get the controller driving the panel.
+    pub fn controller(&self) -> Controller {
+        self.display.controller()
+    }
+
+    /// Get the initialisation configuration.
+    pub fn config(&self) -> &Ssd1306Config {
+        self.display.config()
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    pub fn dimensions(&self) -> (u8, u8) {
+        self.display.dimensions()
+    }
+
+    /// Get the display rotation. Use [`orientation`](Ssd1306Async::orientation) to also see
+    /// whether the display is mirrored.
+    pub fn rotation(&self) -> DisplayRotation {
+        self.display.rotation()
+    }
+
+    /// Get the display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.display.orientation()
+    }
+
+    /// Get the power state of the display.
+    pub fn power_state(&self) -> PowerState {
+        self.display.power_state()
+    }
+
+    /// Get the addressing mode.
+    pub fn addr_mode(&self) -> AddrMode {
+        self.display.addr_mode()
+    }
+
+    /// Get the display brightness.
+    pub fn brightness(&self) -> Brightness {
+        self.display.brightness()
+    }
+
+    /// Whether the display is inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.display.is_inverted()
+    }
+
+    /// Send the initialisation sequence for the given addressing mode.
+    async fn send_init(&mut self, mode: AddrMode) -> Result<(), DisplayError> {
+        let commands = self.display.init_commands(mode)?;
+        commands.send_async(&mut self.display.interface).await?;
+        self.display.initialized(mode);
+
+        Ok(())
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1306Async<DI, SIZE, Uninitialized<MODE>>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Select the controller driving the panel. The default is [`Controller::Ssd1306`].
+    pub fn with_controller(self, controller: Controller) -> Self {
+        Self {
+            display: self.display.with_controller(controller),
+        }
+    }
+
+    /// Set the register values used when the display is initialised. See [`Ssd1306Config`].
+    pub fn with_config(self, config: Ssd1306Config) -> Self {
+        Self {
+            display: self.display.with_config(config),
+        }
+    }
+
+    /// Set the orientation the display is initialised with, replacing the rotation given to
+    /// [`new`](Ssd1306Async::new). See [`Orientation`].
+    pub fn with_orientation(self, orientation: Orientation) -> Self
+    where
+        MODE: InitializedMode,
+    {
+        Self {
+            display: self.display.with_orientation(orientation),
+        }
+    }
+
+    /// Convert the display into a buffered graphics mode, supporting
+    /// [embedded-graphics](https://crates.io/crates/embedded-graphics).
+    ///
+    /// See [`BufferedGraphicsMode`] for more information.
+    pub fn into_buffered_graphics_mode(
+        self,
+    ) -> Ssd1306Async<DI, SIZE, Uninitialized<BufferedGraphicsMode<SIZE>>> {
+        Ssd1306Async {
+            display: self.display.into_buffered_graphics_mode(),
+        }
+    }
+
+    /// Convert the display into a buffered graphics mode that only flushes the bytes that
+    /// changed since they were last sent. It keeps a second copy of the buffer to do so.
+    ///
+    /// See [`BufferedGraphicsMode`] and [`Shadowed`] for more information.
+    pub fn into_shadowed_graphics_mode(
+        self,
+    ) -> Ssd1306Async<DI, SIZE, Uninitialized<BufferedGraphicsMode<SIZE, Shadowed>>> {
+        Ssd1306Async {
+            display: self.display.into_shadowed_graphics_mode(),
+        }
+    }
+
+    /// Convert the display into a text-only, terminal-like mode.
+    ///
+    /// See [`TerminalMode`] for more information.
+    pub fn into_terminal_mode(self) -> Ssd1306Async<DI, SIZE, Uninitialized<TerminalMode>> {
+        Ssd1306Async {
+            display: self.display.into_terminal_mode(),
+        }
+    }
+
+    /// Initialise and configure the display for its mode, returning the initialised display.
+    /// See [`Ssd1306::init`](crate::Ssd1306::init).
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306, Ssd1306Async};
+    ///
+    /// let (mut blocking, mut log) = (CommandLog::new(), CommandLog::new());
+    ///
+    /// Ssd1306::new(&mut blocking, DisplaySize128x32, DisplayRotation::Rotate90)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// embassy_futures::block_on(async {
+    ///     Ssd1306Async::new(&mut log, DisplaySize128x32, DisplayRotation::Rotate90)
+    ///         .into_buffered_graphics_mode()
+    ///         .init()
+    ///         .await
+    ///         .unwrap();
+    /// });
+    ///
+    /// assert_eq!(log, blocking);
+    /// // The display is turned off while it is configured, then on again
+    /// assert_eq!(log.commands().first(), Some(&0xAE));
+    /// assert_eq!(log.commands().last(), Some(&0xAF));
+    /// assert_eq!(log.data_len(), 0);
+    /// ```
+    pub async fn init<E>(self) -> Result<Ssd1306Async<DI, SIZE, MODE>, E>
+    where
+        Ssd1306Async<DI, SIZE, MODE>: DisplayConfigAsync<Error = E>,
+    {
+        let mut display = Ssd1306Async {
+            display: self.display.map_mode(Uninitialized::into_inner),
+        };
+        DisplayConfigAsync::init(&mut display).await?;
+
+        Ok(display)
+    }
+
+    /// Initialise the display in one of the available addressing modes, returning the
+    /// initialised display.
+    ///
+    /// This sends the same command sequence as
+    /// [`Ssd1306::init_with_addr_mode`](crate::Ssd1306::init_with_addr_mode).
+    pub async fn init_with_addr_mode(
+        self,
+        mode: AddrMode,
+    ) -> Result<Ssd1306Async<DI, SIZE, MODE>, DisplayError> {
+        let mut display = Ssd1306Async {
+            display: self.display.map_mode(Uninitialized::into_inner),
+        };
+        display.send_init(mode).await?;
+
+        Ok(display)
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1306Async<DI, SIZE, MODE>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    MODE: InitializedMode,
+{
+    /// Convert the display into a buffered graphics mode, supporting
+    /// [embedded-graphics](https://crates.io/crates/embedded-graphics).
+    ///
+    /// See [`BufferedGraphicsMode`] for more information.
+    pub fn into_buffered_graphics_mode(self) -> Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE>> {
+        Ssd1306Async {
+            display: self.display.into_buffered_graphics_mode(),
+        }
+    }
+
+    /// Convert the display into a buffered graphics mode that only flushes the bytes that
+    /// changed since they were last sent. It keeps a second copy of the buffer to do so.
+    ///
+    /// See [`BufferedGraphicsMode`] and [`Shadowed`] for more information.
+    pub fn into_shadowed_graphics_mode(
+        self,
+    ) -> Ssd1306Async<DI, SIZE, BufferedGraphicsMode<SIZE, Shadowed>> {
+        Ssd1306Async {
+            display: self.display.into_shadowed_graphics_mode(),
+        }
+    }
+
+    /// Convert the display into a text-only, terminal-like mode.
+    ///
+    /// See [`TerminalMode`] for more information.
+    pub fn into_terminal_mode(self) -> Ssd1306Async<DI, SIZE, TerminalMode> {
+        Ssd1306Async {
+            display: self.display.into_terminal_mode(),
+        }
+    }
+
+    /// Initialise the display again in one of the available addressing modes. See
+    /// [`Ssd1306::init_with_addr_mode`](crate::Ssd1306::init_with_addr_mode).
+    pub async fn init_with_addr_mode(&mut self, mode: AddrMode) -> Result<(), DisplayError> {
+        self.send_init(mode).await
+    }
+
+    /// Change the addressing mode. See [`Ssd1306::set_addr_mode`](crate::Ssd1306::set_addr_mode).
+    pub async fn set_addr_mode(&mut self, mode: AddrMode) -> Result<(), Error> {
+        if let Some(commands) = self.display.addr_mode_commands(mode)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.addr_mode = mode;
+        }
+
+        Ok(())
+    }
+
+    /// Send a raw buffer to the display.
+    pub async fn draw(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
+        self.display.interface.send_data(U8(buffer)).await
+    }
+
+    /// Set the display rotation. Mirroring is kept as it is.
+    pub async fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Error> {
+        let mirrored = self.display.orientation.is_mirrored();
+        self.set_orientation(Orientation::new(rotation, mirrored))
+            .await
+    }
+
+    /// Set the display orientation, which combines the rotation and mirroring.
+    ///
+    /// This method does not clear the screen. Buffered graphics modes keep what has been drawn
+    /// at the same coordinates, so it turns with the display, and send all of it again on the
+    /// next flush. See [`Framebuffer::set_orientation`].
+    pub async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Error> {
+        if let Some(commands) = self.display.orientation_commands(orientation)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.orientation_set(orientation);
+        }
+
+        Ok(())
+    }
+
+    /// Change the display brightness.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306, Ssd1306Async};
+    ///
+    /// let (mut blocking, mut log) = (CommandLog::new(), CommandLog::new());
+    ///
+    /// Ssd1306::new(&mut blocking, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .init()
+    ///     .unwrap()
+    ///     .set_brightness(Brightness::BRIGHTEST)
+    ///     .unwrap();
+    ///
+    /// embassy_futures::block_on(async {
+    ///     let mut display = Ssd1306Async::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///         .init()
+    ///         .await
+    ///         .unwrap();
+    ///
+    ///     display.set_brightness(Brightness::BRIGHTEST).await.unwrap();
+    ///     // Setting the same brightness again sends nothing
+    ///     display.set_brightness(Brightness::BRIGHTEST).await.unwrap();
+    ///     assert_eq!(display.brightness(), Brightness::BRIGHTEST);
+    /// });
+    ///
+    /// assert_eq!(log, blocking);
+    /// assert!(log.commands().ends_with(&[0x81, 0xFF]));
+    /// ```
+    pub async fn set_brightness(&mut self, brightness: Brightness) -> Result<(), Error> {
+        if let Some(commands) = self.display.brightness_commands(brightness)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.brightness = brightness;
+        }
+
+        Ok(())
+    }
+
+    /// Turn the display on or off. See
+    /// [`Ssd1306::set_display_on`](crate::Ssd1306::set_display_on).
+    pub async fn set_display_on(&mut self, on: bool) -> Result<(), Error> {
+        if let Some(commands) = self.display.display_on_commands(on, true)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.power = if on { PowerState::On } else { PowerState::Off };
+        }
+
+        Ok(())
+    }
+
+    /// Put the display to sleep, turning off the display and its charge pump. See
+    /// [`Ssd1306::sleep`](crate::Ssd1306::sleep).
+    pub async fn sleep(&mut self) -> Result<(), DisplayError> {
+        if let Some(commands) = self.display.sleep_commands()? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.power = PowerState::Asleep;
+        }
+
+        Ok(())
+    }
+
+    /// Wake the display up after [`sleep`](Self::sleep). See
+    /// [`Ssd1306::wake`](crate::Ssd1306::wake).
+    pub async fn wake<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), DisplayError>
+    where
+        DELAY: DelayNs,
+    {
+        if self.display.power == PowerState::On {
+            return Ok(());
+        }
+
+        if let Some(commands) = self.display.display_on_commands(true, false)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.power = PowerState::On;
+        }
+        delay.delay_ms(WAKE_DELAY_MS).await;
+
+        let commands = self.display.wake_commands()?;
+        commands.send_async(&mut self.display.interface).await
+    }
+
+    /// Set the position in the framebuffer of the display limiting where any sent data should be
+    /// drawn. See [`Ssd1306::set_draw_area`](crate::Ssd1306::set_draw_area).
+    pub async fn set_draw_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), Error> {
+        let commands = self.display.draw_area_commands(start, end)?;
+        commands.send_async(&mut self.display.interface).await?;
+
+        Ok(())
+    }
+
+    /// Set the column address in the framebuffer of the display where any sent data should be
+    /// drawn.
+    pub async fn set_column(&mut self, column: u8) -> Result<(), Error> {
+        let commands = self
+            .display
+            .checked_commands::<1>(&[Command::ColStart(column)])?;
+        commands.send_async(&mut self.display.interface).await?;
+
+        Ok(())
+    }
+
+    /// Set the page address (row 8px high) in the framebuffer of the display where any sent data
+    /// should be drawn. See [`Ssd1306::set_row`](crate::Ssd1306::set_row).
+    pub async fn set_row(&mut self, row: u8) -> Result<(), Error> {
+        let commands = self.display.row_commands(row)?;
+        commands.send_async(&mut self.display.interface).await?;
+
+        Ok(())
+    }
+
+    /// Set the screen pixel on/off inversion
+    pub async fn set_invert(&mut self, invert: bool) -> Result<(), Error> {
+        if let Some(commands) = self.display.invert_commands(invert)? {
+            commands.send_async(&mut self.display.interface).await?;
+            self.display.inverted = invert;
+        }
+
+        Ok(())
+    }
+
+    /// Send the changed parts of a [`Framebuffer`] to the display. See
+    /// [`Ssd1306::flush_from`](crate::Ssd1306::flush_from).
+    pub async fn flush_from(
+        &mut self,
+        framebuffer: &mut Framebuffer<SIZE>,
+    ) -> Result<(), DisplayError> {
+        while let Some(area) = self.display.next_area_from(framebuffer)? {
+            for page in area.pages() {
+                let (commands, data) =
+                    self.display
+                        .area_page(framebuffer.buffer().as_ref(), &area, page)?;
+                commands.send_async(&mut self.display.interface).await?;
+                self.display.interface.send_data(U8(data)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Zero display RAM from `start` to the exclusive `end`, addressing it the way the current
+    /// addressing mode needs.
+    pub(crate) async fn clear_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), Error> {
+        let area = DirtyArea::ram(start, end).ok_or(Error::InvalidArea)?;
+
+        for page in area.pages() {
+            let commands = self.display.area_page_commands(&area, page)?;
+            commands.send_async(&mut self.display.interface).await?;
+            self.draw(area.zeros()).await?;
+        }
+
+        Ok(())
+    }
+}
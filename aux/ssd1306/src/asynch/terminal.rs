@@ -0,0 +1,169 @@
+//! Async terminal mode.
+
+use super::{AsyncWriteOnlyDataCommand, DisplayConfigAsync, Ssd1306Async};
+use crate::{
+    command::AddrMode,
+    mode::{CursorWrapEvent, TerminalMode, TerminalModeError},
+    rotation::{DisplayRotation, Orientation},
+    size::DisplaySize,
+};
+
+impl<DI, SIZE> DisplayConfigAsync for Ssd1306Async<DI, SIZE, TerminalMode>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    type Error = TerminalModeError;
+
+    /// Set the display rotation
+    ///
+    /// This method resets the cursor but does not clear the screen.
+    async fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), TerminalModeError> {
+        self.set_rotation(rot).await?;
+        // Need to reset cursor position, otherwise coordinates can become invalid
+        self.reset_pos().await
+    }
+
+    /// Set the display orientation
+    ///
+    /// This method resets the cursor but does not clear the screen.
+    async fn set_orientation(&mut self, orientation: Orientation) -> Result<(), TerminalModeError> {
+        self.set_orientation(orientation).await?;
+        self.reset_pos().await
+    }
+
+    /// Initialise the display in page mode (i.e. a byte walks down a column of 8 pixels) with
+    /// column 0 on the left and column _(SIZE::Width::U8 - 1)_ on the right, but no automatic line
+    /// wrapping.
+    async fn init(&mut self) -> Result<(), TerminalModeError> {
+        self.init_with_addr_mode(AddrMode::Page).await?;
+        self.reset_pos().await?;
+        Ok(())
+    }
+}
+
+impl<DI, SIZE> Ssd1306Async<DI, SIZE, TerminalMode>
+where
+    DI: AsyncWriteOnlyDataCommand,
+    SIZE: DisplaySize,
+{
+    /// Clear the display and reset the cursor to the top left corner
+    pub async fn clear(&mut self) -> Result<(), TerminalModeError> {
+        let (start, end) = self.display.panel_area();
+        self.clear_area(start, end).await?;
+
+        self.reset_pos().await?;
+
+        Ok(())
+    }
+
+    /// Print a character to the display
+    ///
+    /// ```rust
+    /// use core::fmt::Write;
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306, Ssd1306Async};
+    ///
+    /// let (mut blocking, mut log) = (CommandLog::new(), CommandLog::new());
+    ///
+    /// let mut display = Ssd1306::new(&mut blocking, DisplaySize128x32, DisplayRotation::Rotate0)
+    ///     .into_terminal_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.clear().unwrap();
+    /// write!(display, "Hi\n!\r?").unwrap();
+    /// # drop(display);
+    ///
+    /// embassy_futures::block_on(async {
+    ///     let mut display = Ssd1306Async::new(&mut log, DisplaySize128x32, DisplayRotation::Rotate0)
+    ///         .into_terminal_mode()
+    ///         .init()
+    ///         .await
+    ///         .unwrap();
+    ///     display.clear().await.unwrap();
+    ///     for c in "Hi\n!\r?".chars() {
+    ///         display.print_char(c).await.unwrap();
+    ///     }
+    ///     // The carriage return went back to the start of the second line
+    ///     assert!(matches!(display.position(), Ok((1, 1))));
+    /// });
+    ///
+    /// assert_eq!(log, blocking);
+    /// // One 8 byte glyph per printed character, after clearing 128 columns of 4 pages
+    /// assert_eq!(log.data_len(), 4 * 128 + 4 * 8);
+    /// ```
+    pub async fn print_char(&mut self, c: char) -> Result<(), TerminalModeError> {
+        match c {
+            '\n' => {
+                let CursorWrapEvent(new_line) = self.display.mode.ensure_cursor()?.advance_line();
+                self.set_position(0, new_line).await?;
+            }
+            '\r' => {
+                self.set_column(0).await?;
+                let (_, cur_line) = self.display.mode.ensure_cursor()?.get_position();
+                self.display.mode.ensure_cursor()?.set_position(0, cur_line);
+            }
+            _ => {
+                let bitmap = TerminalMode::glyph(c, self.display.orientation);
+
+                self.draw(&bitmap).await?;
+
+                // Increment character counter and potentially wrap line
+                self.advance_cursor().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Print a string to the display.
+    ///
+    /// This is the async counterpart to the [`core::fmt::Write`] implementation of the blocking
+    /// terminal mode.
+    pub async fn write_str(&mut self, s: &str) -> Result<(), TerminalModeError> {
+        for c in s.chars() {
+            self.print_char(c).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the current cursor position, in character coordinates.
+    /// This is the (column, row) that the next character will be written to.
+    pub fn position(&self) -> Result<(u8, u8), TerminalModeError> {
+        self.display.mode.position()
+    }
+
+    /// Set the cursor position, in character coordinates.
+    /// This is the (column, row) that the next character will be written to.
+    /// If the position is out of bounds, an Err will be returned.
+    pub async fn set_position(&mut self, column: u8, row: u8) -> Result<(), TerminalModeError> {
+        let commands = self.display.position_commands(column, row)?;
+        commands.send_async(&mut self.display.interface).await?;
+        self.display.mode.ensure_cursor()?.set_position(column, row);
+
+        Ok(())
+    }
+
+    /// Reset the draw area and move pointer to the top left corner
+    async fn reset_pos(&mut self) -> Result<(), TerminalModeError> {
+        // Initialise the counter when we know it's valid
+        let dimensions = self.dimensions();
+        self.display.mode.reset_cursor(dimensions);
+
+        // Reset cursor position
+        self.set_position(0, 0).await?;
+
+        Ok(())
+    }
+
+    /// Advance the cursor, automatically wrapping lines and/or screens if necessary
+    async fn advance_cursor(&mut self) -> Result<(), TerminalModeError> {
+        let cursor = self.display.mode.ensure_cursor()?;
+
+        cursor.advance();
+        let (c, r) = cursor.get_position();
+        self.set_position(c, r).await?;
+
+        Ok(())
+    }
+}
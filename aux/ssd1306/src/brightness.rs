@@ -1,5 +1,7 @@
 //! Display brightness
 
+use crate::Error;
+
 /// Struct that holds display brightness
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct Brightness {
@@ -31,23 +33,48 @@ impl Brightness {
 
     /// Create a Brightness object from a precharge period and contrast pair.
     ///
-    /// `precharge` sets the `phase 2` argument of the `0xD9 Set Pre-Charge Period` command and must
-    /// be must be between 1 and 15.
+    /// `precharge` sets the `phase 2` argument of the `0xD9 Set Pre-Charge Period` command and is
+    /// clamped to between 1 and 15.
     /// The effects of this parameter are hardware dependent. For the common 128x64 displays, values
     /// 1 and 2 result in different brightness levels, values above 2 behave the same was as 2.
     /// See section 10.1.17 of the SSD1306 datasheet for more information.
     ///
     /// `contrast` sets the value used in the `0x81 Set Contrast Control` command and must be
     /// between 0 and 255. See section 10.1.7 of the SSD1306 datasheet for more information.
+    ///
+    /// Use [`try_custom`](Self::try_custom) to reject an out of range `precharge` instead.
+    ///
+    /// ```rust
+    /// use ssd1306::prelude::Brightness;
+    ///
+    /// assert_eq!(Brightness::custom(0, 0x2F), Brightness::custom(1, 0x2F));
+    /// assert_eq!(Brightness::custom(16, 0x2F), Brightness::custom(15, 0x2F));
+    /// ```
     pub const fn custom(precharge: u8, contrast: u8) -> Self {
-        debug_assert!(
-            0 < precharge && precharge <= 15,
-            "Precharge value must be between 1 and 15"
-        );
+        let precharge = if precharge == 0 {
+            1
+        } else if precharge > 15 {
+            15
+        } else {
+            precharge
+        };
 
         Self {
             precharge,
             contrast,
         }
     }
+
+    /// Create a Brightness object like [`custom`](Self::custom), returning
+    /// [`Error::OutOfBounds`] if `precharge` isn't between 1 and 15.
+    pub const fn try_custom(precharge: u8, contrast: u8) -> Result<Self, Error> {
+        if precharge == 0 || precharge > 15 {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(Self {
+            precharge,
+            contrast,
+        })
+    }
 }
@@ -2,12 +2,19 @@
 
 // Shamefully taken from https://github.com/EdgewaterDevelopment/rust-ssd1306
 
-use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+#[cfg(feature = "async")]
+use crate::asynch::AsyncWriteOnlyDataCommand;
+use crate::{controller::Controller, error::Error};
+use core::convert::TryFrom;
+use display_interface::{
+    DataFormat::{self, U8},
+    DisplayError, WriteOnlyDataCommand,
+};
 
 /// SSD1306 Commands
 
 /// Commands
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Command {
     /// Set contrast. Higher number is higher contrast. Default = 0x7F
     Contrast(u8),
@@ -98,14 +105,30 @@ impl Command {
     where
         DI: WriteOnlyDataCommand,
     {
+        let (data, len) = self.encode();
+
+        iface.send_commands(U8(&data[..len]))
+    }
+
+    /// Send command to SSD1306 over an async interface
+    #[cfg(feature = "async")]
+    pub async fn send_async<DI>(self, iface: &mut DI) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+    {
+        let (data, len) = self.encode();
+
+        iface.send_commands(U8(&data[..len])).await
+    }
+
+    /// Encode the command into its bytes, returning a buffer and the number of bytes used.
+    pub(crate) const fn encode(self) -> ([u8; 7], usize) {
         match self {
-            // Command::Contrast(val) => Self::send_commands(iface, &[0x81, val]),
-            Command::Contrast(_val) => Self::send_commands(iface, &[0x81, 0x8F]),
-            Command::AllOn(on) => Self::send_commands(iface, &[0xA4 | (on as u8)]),
-            Command::Invert(inv) => Self::send_commands(iface, &[0xA6 | (inv as u8)]),
-            Command::DisplayOn(on) => Self::send_commands(iface, &[0xAE | (on as u8)]),
-            Command::HScrollSetup(dir, start, end, rate) => Self::send_commands(
-                iface,
+            Command::Contrast(val) => Self::pad(&[0x81, val]),
+            Command::AllOn(on) => Self::pad(&[0xA4 | (on as u8)]),
+            Command::Invert(inv) => Self::pad(&[0xA6 | (inv as u8)]),
+            Command::DisplayOn(on) => Self::pad(&[0xAE | (on as u8)]),
+            Command::HScrollSetup(dir, start, end, rate) => Self::pad(
                 &[
                     0x26 | (dir as u8),
                     0,
@@ -116,8 +139,7 @@ impl Command {
                     0xFF,
                 ],
             ),
-            Command::VHScrollSetup(dir, start, end, rate, offset) => Self::send_commands(
-                iface,
+            Command::VHScrollSetup(dir, start, end, rate, offset) => Self::pad(
                 &[
                     0x28 | (dir as u8),
                     0,
@@ -127,62 +149,479 @@ impl Command {
                     offset,
                 ],
             ),
-            Command::EnableScroll(en) => Self::send_commands(iface, &[0x2E | (en as u8)]),
-            Command::VScrollArea(above, lines) => Self::send_commands(iface, &[0xA3, above, lines]),
-            Command::LowerColStart(addr) => Self::send_commands(iface, &[0xF & addr]),
-            // Command::UpperColStart(addr) => Self::send_commands(iface, &[0x10 | (0xF & addr)]),
-            Command::UpperColStart(addr) => Self::send_commands(iface, &[(0x10 | (0xF & addr >> 4)) + 2]),
+            Command::EnableScroll(en) => Self::pad(&[0x2E | (en as u8)]),
+            Command::VScrollArea(above, lines) => Self::pad(&[0xA3, above, lines]),
+            Command::LowerColStart(addr) => Self::pad(&[0xF & addr]),
+            Command::UpperColStart(addr) => Self::pad(&[0x10 | (0xF & addr)]),
             Command::ColStart(addr) => {
-                Self::send_commands(iface, &[0xF & addr, 0x10 | (0xF & (addr >> 4))])
+                Self::pad(&[0xF & addr, 0x10 | (0xF & (addr >> 4))])
             }
-            Command::AddressMode(mode) => Self::send_commands(iface, &[0x20, mode as u8]),
-            Command::ColumnAddress(start, end) => Self::send_commands(iface, &[0x21, start, end]),
+            Command::AddressMode(mode) => Self::pad(&[0x20, mode as u8]),
+            Command::ColumnAddress(start, end) => Self::pad(&[0x21, start, end]),
             Command::PageAddress(_start, _end) => {
-                Self::send_commands(iface, &[0x22, 0, 5])
+                Self::pad(&[0x22, 0, 5])
             }
             Command::PageAddressNum(start, end) => {
-                Self::send_commands(iface, &[0x22, start, end])
+                Self::pad(&[0x22, start, end])
             }
-            Command::PageStart(page) => Self::send_commands(iface, &[0xB0 | (page as u8)]),
-            Command::StartLine(line) => Self::send_commands(iface, &[0x40 | (0x3F & line)]),
-            Command::SegmentRemap(remap) => Self::send_commands(iface, &[0xA0 | (remap as u8)]),
-            Command::Multiplex(ratio) => Self::send_commands(iface, &[0xA8, ratio]),
-            Command::ReverseComDir(rev) => Self::send_commands(iface, &[0xC0 | ((rev as u8) << 3)]),
-            Command::DisplayOffset(offset) => Self::send_commands(iface, &[0xD3, offset]),
-            Command::ComPinConfig(_alt, _lr) => {
-                // Self::send_commands(iface, &[0xDA, 0x2 | ((alt as u8) << 4) | ((lr as u8) << 5)])
-                Self::send_commands(iface, &[0xDA, 0x12])
+            Command::PageStart(page) => Self::pad(&[0xB0 | (page as u8)]),
+            Command::StartLine(line) => Self::pad(&[0x40 | (0x3F & line)]),
+            Command::SegmentRemap(remap) => Self::pad(&[0xA0 | (remap as u8)]),
+            Command::Multiplex(ratio) => Self::pad(&[0xA8, ratio]),
+            Command::ReverseComDir(rev) => Self::pad(&[0xC0 | ((rev as u8) << 3)]),
+            Command::DisplayOffset(offset) => Self::pad(&[0xD3, offset]),
+            Command::ComPinConfig(alt, lr) => {
+                Self::pad(&[0xDA, 0x2 | ((alt as u8) << 4) | ((lr as u8) << 5)])
             }
             Command::DisplayClockDiv(fosc, div) => {
-                Self::send_commands(iface, &[0xD5, ((0xF & fosc) << 4) | (0xF & div)])
+                Self::pad(&[0xD5, ((0xF & fosc) << 4) | (0xF & div)])
             }
-            Command::PreChargePeriod(_phase1, _phase2) => {
-                // Self::send_commands(iface, &[0xD9, ((0xF & phase2) << 4) | (0xF & phase1)])
-                Self::send_commands(iface, &[0xD9, 0xF1])
+            Command::PreChargePeriod(phase1, phase2) => {
+                Self::pad(&[0xD9, ((0xF & phase2) << 4) | (0xF & phase1)])
             }
             Command::VcomhDeselect(level) => {
-                Self::send_commands(iface, &[0xDB, (level as u8) << 4])
+                Self::pad(&[0xDB, (level as u8) << 4])
             }
-            Command::Noop => Self::send_commands(iface, &[0xE3]),
+            Command::Noop => Self::pad(&[0xE3]),
             Command::ChargePump(en) => {
-                Self::send_commands(iface, &[0x8D, 0x10 | ((en as u8) << 2)])
+                Self::pad(&[0x8D, 0x10 | ((en as u8) << 2)])
             }
             Command::InternalIref(en, current) => {
-                Self::send_commands(iface, &[0xAD, ((current as u8) << 5) | ((en as u8) << 4)])
+                Self::pad(&[0xAD, ((current as u8) << 5) | ((en as u8) << 4)])
+            }
+        }
+    }
+
+    const fn pad(data: &[u8]) -> ([u8; 7], usize) {
+        let mut padded = [0; 7];
+
+        // `copy_from_slice` isn't usable in const fns
+        let mut i = 0;
+        while i < data.len() {
+            padded[i] = data[i];
+            i += 1;
+        }
+
+        (padded, data.len())
+    }
+
+    /// Decode the command at the start of `bytes`.
+    ///
+    /// This is the inverse of [`send`](Command::send). On success, the decoded command and the
+    /// number of bytes it occupies are returned. Error offsets are relative to the start of
+    /// `bytes`.
+    ///
+    /// Some commands share an encoding, so they are always decoded to the same variant:
+    ///
+    /// - `0x22` is decoded to [`PageAddressNum`](Command::PageAddressNum).
+    /// - [`ColStart`](Command::ColStart) is sent as two separate commands and is decoded to
+    ///   [`LowerColStart`](Command::LowerColStart) and [`UpperColStart`](Command::UpperColStart),
+    ///   each holding the 4 bit nibble sent to the display.
+    ///
+    /// ```rust
+    /// use ssd1306::command::{AddrMode, Command, DecodeError};
+    ///
+    /// assert_eq!(
+    ///     Command::decode(&[0x21, 32, 95, 0xAF]),
+    ///     Ok((Command::ColumnAddress(32, 95), 3))
+    /// );
+    /// assert_eq!(
+    ///     Command::decode(&[0x20, 0x00]),
+    ///     Ok((Command::AddressMode(AddrMode::Horizontal), 2))
+    /// );
+    /// assert_eq!(
+    ///     Command::decode(&[0xD5]),
+    ///     Err(DecodeError::Truncated { offset: 0, opcode: 0xD5 })
+    /// );
+    /// assert_eq!(
+    ///     Command::decode(&[0xFF]),
+    ///     Err(DecodeError::UnknownCommand { offset: 0, opcode: 0xFF })
+    /// );
+    /// ```
+    pub fn decode(bytes: &[u8]) -> Result<(Command, usize), DecodeError> {
+        let opcode = *bytes.first().ok_or(DecodeError::Empty)?;
+
+        let arg = |n: usize| {
+            bytes
+                .get(n)
+                .copied()
+                .ok_or(DecodeError::Truncated { offset: 0, opcode })
+        };
+        let invalid = |n: usize| DecodeError::InvalidArgument { offset: n, opcode };
+        // Any 4 bit page is decoded, whether or not the display has that many
+        let page = |n: usize| match arg(n)? {
+            page @ 0..=15 => Ok(Page::from_bits(page)),
+            _ => Err(invalid(n)),
+        };
+
+        let decoded = match opcode {
+            0x00..=0x0F => (Command::LowerColStart(opcode & 0x0F), 1),
+            0x10..=0x1F => (Command::UpperColStart(opcode & 0x0F), 1),
+            0x20 => {
+                let mode = match arg(1)? {
+                    0b00 => AddrMode::Horizontal,
+                    0b01 => AddrMode::Vertical,
+                    0b10 => AddrMode::Page,
+                    _ => return Err(invalid(1)),
+                };
+
+                (Command::AddressMode(mode), 2)
+            }
+            0x21 => (Command::ColumnAddress(arg(1)?, arg(2)?), 3),
+            0x22 => (Command::PageAddressNum(arg(1)?, arg(2)?), 3),
+            0x26 | 0x27 => {
+                // Bytes 1, 5 and 6 are dummy bytes
+                arg(6)?;
+
+                let dir = if opcode & 1 == 0 {
+                    HScrollDir::LeftToRight
+                } else {
+                    HScrollDir::RightToLeft
+                };
+
+                (
+                    Command::HScrollSetup(dir, page(2)?, page(4)?, NFrames::from_bits(arg(3)?)),
+                    7,
+                )
+            }
+            0x29 | 0x2A => {
+                // Byte 1 is a dummy byte
+                arg(5)?;
+
+                let dir = if opcode == 0x29 {
+                    VHScrollDir::VerticalRight
+                } else {
+                    VHScrollDir::VerticalLeft
+                };
+
+                (
+                    Command::VHScrollSetup(
+                        dir,
+                        page(2)?,
+                        page(4)?,
+                        NFrames::from_bits(arg(3)?),
+                        arg(5)?,
+                    ),
+                    6,
+                )
+            }
+            0x2E | 0x2F => (Command::EnableScroll(opcode & 1 != 0), 1),
+            0x40..=0x7F => (Command::StartLine(opcode & 0x3F), 1),
+            0x81 => (Command::Contrast(arg(1)?), 2),
+            0x8D => (Command::ChargePump(arg(1)? & 0x04 != 0), 2),
+            0xA0 | 0xA1 => (Command::SegmentRemap(opcode & 1 != 0), 1),
+            0xA3 => (Command::VScrollArea(arg(1)?, arg(2)?), 3),
+            0xA4 | 0xA5 => (Command::AllOn(opcode & 1 != 0), 1),
+            0xA6 | 0xA7 => (Command::Invert(opcode & 1 != 0), 1),
+            0xA8 => (Command::Multiplex(arg(1)?), 2),
+            0xAD => {
+                let iref = arg(1)?;
+
+                (
+                    Command::InternalIref(iref & 0x10 != 0, iref & 0x20 != 0),
+                    2,
+                )
+            }
+            0xAE | 0xAF => (Command::DisplayOn(opcode & 1 != 0), 1),
+            0xB0..=0xBF => (Command::PageStart(Page::from_bits(opcode)), 1),
+            0xC0 | 0xC8 => (Command::ReverseComDir(opcode & 0x08 != 0), 1),
+            0xD3 => (Command::DisplayOffset(arg(1)?), 2),
+            0xD5 => {
+                let clock = arg(1)?;
+
+                (Command::DisplayClockDiv(clock >> 4, clock & 0x0F), 2)
+            }
+            0xD9 => {
+                let period = arg(1)?;
+
+                (Command::PreChargePeriod(period & 0x0F, period >> 4), 2)
+            }
+            0xDA => {
+                let config = arg(1)?;
+
+                (
+                    Command::ComPinConfig(config & 0x10 != 0, config & 0x20 != 0),
+                    2,
+                )
+            }
+            0xDB => {
+                let level = match arg(1)? >> 4 {
+                    0b001 => VcomhLevel::V065,
+                    0b010 => VcomhLevel::V077,
+                    0b011 => VcomhLevel::V083,
+                    0b100 => VcomhLevel::Auto,
+                    _ => return Err(invalid(1)),
+                };
+
+                (Command::VcomhDeselect(level), 2)
+            }
+            0xE3 => (Command::Noop, 1),
+            _ => return Err(DecodeError::UnknownCommand { offset: 0, opcode }),
+        };
+
+        Ok(decoded)
+    }
+
+    /// Decode a stream of command bytes into an iterator of [`Command`]s.
+    ///
+    /// Decoding stops after the first error. This can be used to pretty print command streams
+    /// captured from a bus, e.g. the bytes following a `0x00` control byte in an I2C write.
+    ///
+    /// ```rust
+    /// use ssd1306::command::{Command, DecodeError};
+    ///
+    /// let mut commands = Command::decode_all(&[0xAE, 0xA8, 47, 0xD3]);
+    ///
+    /// assert_eq!(commands.next(), Some(Ok(Command::DisplayOn(false))));
+    /// assert_eq!(commands.next(), Some(Ok(Command::Multiplex(47))));
+    /// assert_eq!(
+    ///     commands.next(),
+    ///     Some(Err(DecodeError::Truncated { offset: 3, opcode: 0xD3 }))
+    /// );
+    /// assert_eq!(commands.next(), None);
+    /// ```
+    pub fn decode_all(bytes: &[u8]) -> CommandDecoder<'_> {
+        CommandDecoder { bytes, offset: 0 }
+    }
+}
+
+/// Errors that can occur when decoding command bytes.
+///
+/// All offsets are relative to the start of the decoded byte slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// There were no bytes to decode.
+    Empty,
+    /// The byte at `offset` is not a known command.
+    UnknownCommand {
+        /// Position of the unknown byte
+        offset: usize,
+        /// The unknown byte
+        opcode: u8,
+    },
+    /// The command starting at `offset` needs more argument bytes than were available.
+    Truncated {
+        /// Position of the command byte
+        offset: usize,
+        /// The command byte
+        opcode: u8,
+    },
+    /// The argument byte at `offset` is out of range for its command.
+    InvalidArgument {
+        /// Position of the invalid argument byte
+        offset: usize,
+        /// The command byte
+        opcode: u8,
+    },
+}
+
+impl DecodeError {
+    fn shifted(self, by: usize) -> Self {
+        match self {
+            DecodeError::Empty => DecodeError::Empty,
+            DecodeError::UnknownCommand { offset, opcode } => DecodeError::UnknownCommand {
+                offset: offset + by,
+                opcode,
+            },
+            DecodeError::Truncated { offset, opcode } => DecodeError::Truncated {
+                offset: offset + by,
+                opcode,
+            },
+            DecodeError::InvalidArgument { offset, opcode } => DecodeError::InvalidArgument {
+                offset: offset + by,
+                opcode,
+            },
+        }
+    }
+}
+
+/// Iterator over the commands in a byte stream.
+///
+/// Created by [`Command::decode_all`].
+#[derive(Debug, Clone)]
+pub struct CommandDecoder<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl Iterator for CommandDecoder<'_> {
+    type Item = Result<Command, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = self.bytes.get(self.offset..).filter(|rest| !rest.is_empty())?;
+
+        match Command::decode(remaining) {
+            Ok((command, len)) => {
+                self.offset += len;
+
+                Some(Ok(command))
+            }
+            Err(e) => {
+                let e = e.shifted(self.offset);
+
+                // Stop decoding after the first error
+                self.offset = self.bytes.len();
+
+                Some(Err(e))
             }
         }
     }
+}
 
-    fn send_commands<DI>(iface: &mut DI, data: &[u8]) -> Result<(), DisplayError>
+/// Longest command stream sent in one call to
+/// [`send_commands`](WriteOnlyDataCommand::send_commands). The I2C interfaces copy commands into
+/// an 8 byte buffer along with the control byte.
+const MAX_STREAM_LEN: usize = 7;
+
+/// Fixed capacity buffer that collects commands and sends them together.
+///
+/// Every call to [`Command::send`] is a separate bus transaction. A batch packs consecutive
+/// commands into command streams of up to 7 bytes, which is as much as the I2C interfaces accept
+/// at once, so sending a batch takes far fewer transactions. `N` is the number of streams the
+/// batch can hold.
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// # let mut interface = StubInterface;
+/// use ssd1306::command::{Command, CommandBatch};
+///
+/// let mut batch = CommandBatch::<2>::new();
+/// batch.push(Command::DisplayOn(false)).unwrap();
+/// batch.push(Command::Contrast(0x7F)).unwrap();
+/// batch.push(Command::Invert(true)).unwrap();
+/// batch.push(Command::DisplayOn(true)).unwrap();
+///
+/// // All four commands fit in a single stream
+/// assert_eq!(batch.streams().count(), 1);
+/// batch.send(&mut interface).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct CommandBatch<const N: usize> {
+    streams: [([u8; MAX_STREAM_LEN], usize); N],
+    len: usize,
+}
+
+impl<const N: usize> Default for CommandBatch<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> CommandBatch<N> {
+    /// Create an empty batch.
+    pub const fn new() -> Self {
+        Self {
+            streams: [([0; MAX_STREAM_LEN], 0); N],
+            len: 0,
+        }
+    }
+
+    /// Add a command to the batch.
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the batch is full.
+    pub fn push(&mut self, command: Command) -> Result<(), DisplayError> {
+        let (data, len) = command.encode();
+
+        self.push_bytes(&data[..len])
+    }
+
+    /// Add a command, rejecting it if the controller doesn't support it.
+    pub(crate) fn push_checked(
+        &mut self,
+        command: Command,
+        controller: Controller,
+    ) -> Result<(), DisplayError> {
+        if !controller.supports(command) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+
+        self.push(command)
+    }
+
+    /// Add an encoded command of at most 7 bytes, keeping it in one stream.
+    fn push_bytes(&mut self, bytes: &[u8]) -> Result<(), DisplayError> {
+        if bytes.len() > MAX_STREAM_LEN {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        let fits_current = self
+            .len
+            .checked_sub(1)
+            .is_some_and(|last| self.streams[last].1 + bytes.len() <= MAX_STREAM_LEN);
+
+        if !fits_current {
+            if self.len == N {
+                return Err(DisplayError::OutOfBoundsError);
+            }
+            self.len += 1;
+        }
+
+        let (data, len) = &mut self.streams[self.len - 1];
+        data[*len..*len + bytes.len()].copy_from_slice(bytes);
+        *len += bytes.len();
+
+        Ok(())
+    }
+
+    /// Whether no commands have been added.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Remove all commands from the batch.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.streams = [([0; MAX_STREAM_LEN], 0); N];
+    }
+
+    /// Iterate over the encoded command streams that [`send`](Self::send) sends.
+    pub fn streams(&self) -> impl Iterator<Item = &[u8]> {
+        self.streams[..self.len]
+            .iter()
+            .map(|(data, len)| &data[..*len])
+    }
+
+    /// Send the commands in the batch, one stream per transaction.
+    pub fn send<DI>(&self, iface: &mut DI) -> Result<(), DisplayError>
     where
         DI: WriteOnlyDataCommand,
     {
-        iface.send_commands(U8(data))
+        self.streams()
+            .try_for_each(|stream| iface.send_commands(U8(stream)))
+    }
+
+    /// Send the commands in the batch over an async interface, one stream per transaction.
+    #[cfg(feature = "async")]
+    pub async fn send_async<DI>(&self, iface: &mut DI) -> Result<(), DisplayError>
+    where
+        DI: AsyncWriteOnlyDataCommand,
+    {
+        for stream in self.streams() {
+            iface.send_commands(U8(stream)).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Collects the commands sent by code written against a display interface, such as
+/// [`DisplaySize::configure`](crate::size::DisplaySize::configure). Each call must contain at most
+/// 7 bytes. Data can't be batched and is rejected.
+impl<const N: usize> WriteOnlyDataCommand for CommandBatch<N> {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            U8(bytes) => self.push_bytes(bytes),
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, _buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        Err(DisplayError::DataFormatNotImplemented)
     }
 }
 
 /// Horizontal Scroll Direction
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub enum HScrollDir {
     /// Left to right
@@ -192,7 +631,7 @@ pub enum HScrollDir {
 }
 
 /// Vertical and horizontal scroll dir
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub enum VHScrollDir {
     /// Vertical and right horizontal
@@ -202,7 +641,7 @@ pub enum VHScrollDir {
 }
 
 /// Display page
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Page {
     /// Page 0
     Page0 = 0b0000,
@@ -238,9 +677,20 @@ pub enum Page {
     Page15 = 0b1111,
 }
 
-impl From<u8> for Page {
-    fn from(val: u8) -> Page {
-        match val / 8 {
+impl Page {
+    /// Get the page of display RAM with the given index, e.g. `3` returns [`Page::Page3`].
+    /// Display RAM has 8 pages, so `None` is returned for indices past 7.
+    pub(crate) fn from_index(index: u8) -> Option<Page> {
+        if index < 8 {
+            Some(Page::from_bits(index))
+        } else {
+            None
+        }
+    }
+
+    /// Get the page from the lower 4 bits of `bits`.
+    const fn from_bits(bits: u8) -> Page {
+        match bits & 0x0F {
             0 => Page::Page0,
             1 => Page::Page1,
             2 => Page::Page2,
@@ -256,14 +706,32 @@ impl From<u8> for Page {
             12 => Page::Page12,
             13 => Page::Page13,
             14 => Page::Page14,
-            15 => Page::Page15,
-            _ => panic!("Page too high"),
+            _ => Page::Page15,
         }
     }
 }
 
+/// Get the page containing the given row, e.g. row `20` is in [`Page::Page2`].
+///
+/// Returns [`Error::OutOfBounds`] for rows past the 64 rows of display RAM.
+///
+/// ```rust
+/// use core::convert::TryFrom;
+/// use ssd1306::{command::Page, Error};
+///
+/// assert_eq!(Page::try_from(20).unwrap(), Page::Page2);
+/// assert!(matches!(Page::try_from(64), Err(Error::OutOfBounds)));
+/// ```
+impl TryFrom<u8> for Page {
+    type Error = Error;
+
+    fn try_from(row: u8) -> Result<Page, Error> {
+        Page::from_index(row / 8).ok_or(Error::OutOfBounds)
+    }
+}
+
 /// Frame interval
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
 pub enum NFrames {
     /// 2 Frames
@@ -284,6 +752,22 @@ pub enum NFrames {
     F256 = 0b011,
 }
 
+impl NFrames {
+    /// Get the frame interval from the lower 3 bits of `bits`.
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b111 {
+            0b111 => NFrames::F2,
+            0b100 => NFrames::F3,
+            0b101 => NFrames::F4,
+            0b000 => NFrames::F5,
+            0b110 => NFrames::F25,
+            0b001 => NFrames::F64,
+            0b010 => NFrames::F128,
+            _ => NFrames::F256,
+        }
+    }
+}
+
 /// Address mode
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[allow(dead_code)]
@@ -297,7 +781,7 @@ pub enum AddrMode {
 }
 
 /// Vcomh Deselect level
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum VcomhLevel {
     /// 0.65 * Vcc
@@ -0,0 +1,598 @@
+//! Display initialisation configuration.
+
+use crate::{
+    brightness::Brightness,
+    command::{AddrMode, Command, CommandBatch, VcomhLevel},
+    controller::Controller,
+    rotation::Orientation,
+    size::DisplaySize,
+    CheckedInterface,
+};
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+
+/// Maximum number of raw commands that can be appended to the initialisation sequence.
+const MAX_RAW_COMMANDS: usize = 4;
+
+/// Maximum length in bytes of a raw command, including its arguments.
+const MAX_RAW_COMMAND_LEN: usize = 7;
+
+/// Number of command streams the init sequence is batched into. About 30 bytes of commands, up
+/// to 4 raw commands and the display size's configuration fit comfortably.
+pub(crate) const INIT_STREAMS: usize = 16;
+
+/// Registers written by [`init`](crate::mode::DisplayConfig::init).
+///
+/// Panels from different suppliers often need slightly different settings. The defaults match
+/// the values the driver has always used, and anything left unset falls back to the
+/// [`Controller`]'s default. The configuration is checked before any command is sent, so an
+/// invalid value leaves the display untouched.
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use ssd1306::{command::VcomhLevel, prelude::*, Ssd1306, Ssd1306Config};
+///
+/// let config = Ssd1306Config::new()
+///     .with_clock(0xF, 0x0)
+///     .with_vcomh(VcomhLevel::V083)
+///     .with_brightness(Brightness::BRIGHT)
+///     // Vendor specific command the driver doesn't know about
+///     .with_raw_command(&[0xAD, 0x30]);
+///
+/// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .with_config(config)
+///     .into_buffered_graphics_mode()
+///     .init()
+///     .unwrap();
+/// ```
+///
+/// Invalid values are rejected when the display is initialised:
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+/// use ssd1306::{prelude::*, Ssd1306, Ssd1306Config};
+///
+/// let display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .with_config(Ssd1306Config::new().with_start_line(64))
+///     .into_buffered_graphics_mode();
+///
+/// assert!(matches!(display.init(), Err(DisplayError::InvalidFormatError)));
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Ssd1306Config {
+    clock: (u8, u8),
+    display_offset: u8,
+    start_line: u8,
+    external_vcc: bool,
+    vcomh: Option<VcomhLevel>,
+    precharge_phase1: Option<u8>,
+    brightness: Brightness,
+    internal_iref: Option<(bool, bool)>,
+    dim_on_sleep: bool,
+    raw_commands: [([u8; MAX_RAW_COMMAND_LEN], usize); MAX_RAW_COMMANDS],
+    raw_len: usize,
+    raw_overflow: bool,
+}
+
+impl Default for Ssd1306Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Ssd1306Config {
+    /// Create the default configuration.
+    pub const fn new() -> Self {
+        Self {
+            clock: (0x8, 0x0),
+            display_offset: 0,
+            start_line: 0,
+            external_vcc: false,
+            vcomh: None,
+            precharge_phase1: None,
+            brightness: Brightness::NORMAL,
+            internal_iref: None,
+            dim_on_sleep: false,
+            raw_commands: [([0; MAX_RAW_COMMAND_LEN], 0); MAX_RAW_COMMANDS],
+            raw_len: 0,
+            raw_overflow: false,
+        }
+    }
+
+    /// Set the display clock. See [`Command::DisplayClockDiv`]. Both values must be in the range
+    /// 0 - 15.
+    pub const fn with_clock(self, fosc: u8, div: u8) -> Self {
+        Self {
+            clock: (fosc, div),
+            ..self
+        }
+    }
+
+    /// Set the vertical shift from 0-63. See [`Command::DisplayOffset`].
+    pub const fn with_display_offset(self, display_offset: u8) -> Self {
+        Self {
+            display_offset,
+            ..self
+        }
+    }
+
+    /// Set the display start line from 0-63. See [`Command::StartLine`].
+    pub const fn with_start_line(self, start_line: u8) -> Self {
+        Self { start_line, ..self }
+    }
+
+    /// Select whether the panel is powered from an external VCC supply. If so, the charge pump is
+    /// turned off and the precharge phase 1 defaults to 2.
+    pub const fn with_external_vcc(self, external_vcc: bool) -> Self {
+        Self {
+            external_vcc,
+            ..self
+        }
+    }
+
+    /// Set the VCOMH deselect level instead of using the controller's default.
+    pub const fn with_vcomh(self, vcomh: VcomhLevel) -> Self {
+        Self {
+            vcomh: Some(vcomh),
+            ..self
+        }
+    }
+
+    /// Set phase 1 of the precharge period, from 1-15, instead of using the controller's
+    /// default. Phase 2 is set by the [`Brightness`].
+    pub const fn with_precharge_phase1(self, phase1: u8) -> Self {
+        Self {
+            precharge_phase1: Some(phase1),
+            ..self
+        }
+    }
+
+    /// Set the brightness the display starts with.
+    pub const fn with_brightness(self, brightness: Brightness) -> Self {
+        Self { brightness, ..self }
+    }
+
+    /// Select the internal or external current reference. See [`Command::InternalIref`].
+    pub const fn with_internal_iref(self, enabled: bool, high_current: bool) -> Self {
+        Self {
+            internal_iref: Some((enabled, high_current)),
+            ..self
+        }
+    }
+
+    /// Set the contrast to 0 before the display goes to sleep and restore it once the display
+    /// has woken up, so the panel doesn't flash while the charge pump stops and starts. See
+    /// [`Ssd1306::sleep`](crate::Ssd1306::sleep).
+    pub const fn with_dim_on_sleep(self, dim_on_sleep: bool) -> Self {
+        Self {
+            dim_on_sleep,
+            ..self
+        }
+    }
+
+    /// Append a raw command, including its arguments, to the end of the initialisation
+    /// sequence. It is sent as is, so vendor specific commands can be used.
+    ///
+    /// Up to 4 commands of at most 7 bytes each can be added. Anything more makes the
+    /// configuration invalid.
+    pub fn with_raw_command(mut self, command: &[u8]) -> Self {
+        match self.raw_commands.get_mut(self.raw_len) {
+            Some((data, len)) if command.len() <= data.len() => {
+                data[..command.len()].copy_from_slice(command);
+                *len = command.len();
+                self.raw_len += 1;
+            }
+            _ => self.raw_overflow = true,
+        }
+
+        self
+    }
+
+    /// Check the configuration against the given controller.
+    ///
+    /// Returns [`DisplayError::InvalidFormatError`] if a value is out of range or needs a
+    /// command the controller doesn't have, and [`DisplayError::OutOfBoundsError`] if too many
+    /// raw commands were added.
+    pub const fn validate(&self, controller: Controller) -> Result<(), DisplayError> {
+        if self.raw_overflow {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+
+        let (fosc, div) = self.clock;
+        let in_range = fosc <= 0xF
+            && div <= 0xF
+            && self.display_offset <= 63
+            && self.start_line <= 63
+            && matches!(self.precharge_phase1, None | Some(1..=15))
+            && matches!(self.brightness.precharge, 1..=15);
+
+        let iref_supported = match self.internal_iref {
+            Some((enabled, high_current)) => {
+                controller.supports(Command::InternalIref(enabled, high_current))
+            }
+            None => true,
+        };
+
+        if in_range && iref_supported {
+            Ok(())
+        } else {
+            Err(DisplayError::InvalidFormatError)
+        }
+    }
+
+    /// Number of bytes in the [`init_sequence`](Self::init_sequence) for these settings.
+    ///
+    /// # Panics
+    ///
+    /// In the same cases as [`init_sequence`](Self::init_sequence).
+    pub const fn init_sequence_len<SIZE: DisplaySize>(
+        &self,
+        controller: Controller,
+        orientation: Orientation,
+        mode: AddrMode,
+    ) -> usize {
+        self.build_sequence::<SIZE, 0>(controller, orientation, mode).len
+    }
+
+    /// Build the command bytes [`init_with_addr_mode`](crate::Ssd1306::init_with_addr_mode)
+    /// sends for a display of type `SIZE`, so they can be sent in one transaction or checked on
+    /// the host. `N` must be [`init_sequence_len`](Self::init_sequence_len).
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::{CommandLog, I2cStub};
+    /// # let mut i2c = I2cStub;
+    /// use embedded_hal::i2c::I2c;
+    /// use ssd1306::{
+    ///     command::AddrMode, controller::Controller, prelude::*, Ssd1306, Ssd1306Config,
+    /// };
+    ///
+    /// const CONFIG: Ssd1306Config = Ssd1306Config::new().with_brightness(Brightness::BRIGHT);
+    /// const LEN: usize = CONFIG.init_sequence_len::<DisplaySize128x64>(
+    ///     Controller::Ssd1306,
+    ///     Orientation::Rotate0,
+    ///     AddrMode::Horizontal,
+    /// );
+    /// const INIT: [u8; LEN] = CONFIG.init_sequence::<DisplaySize128x64, LEN>(
+    ///     Controller::Ssd1306,
+    ///     Orientation::Rotate0,
+    ///     AddrMode::Horizontal,
+    /// );
+    ///
+    /// // The display interfaces split commands into short transactions, so write the whole
+    /// // sequence after a 0x00 control byte directly
+    /// let mut write = [0; LEN + 1];
+    /// write[1..].copy_from_slice(&INIT);
+    /// i2c.write(0x3C, &write).unwrap();
+    ///
+    /// // The driver sends the same bytes
+    /// let mut log = CommandLog::new();
+    /// let display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_config(CONFIG)
+    ///     .init_with_addr_mode(AddrMode::Horizontal)
+    ///     .unwrap();
+    /// drop(display);
+    ///
+    /// assert_eq!(log.commands(), &INIT[..]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `N` is not the length of the sequence, the configuration is invalid, the panel doesn't
+    /// fit in the controller's display RAM or the controller doesn't support a command or the
+    /// addressing mode. Also if `SIZE` is only configured at runtime, as
+    /// [`DisplaySizeCustom`](crate::size::DisplaySizeCustom) and
+    /// [`DisplaySizeDynamic`](crate::size::DisplaySizeDynamic) are (see
+    /// [`DisplaySize::CONFIGURE_COMMANDS`]); their sequence depends on values only known at
+    /// runtime. When evaluated in a constant, these are compile time errors.
+    pub const fn init_sequence<SIZE: DisplaySize, const N: usize>(
+        &self,
+        controller: Controller,
+        orientation: Orientation,
+        mode: AddrMode,
+    ) -> [u8; N] {
+        let sequence = self.build_sequence::<SIZE, N>(controller, orientation, mode);
+
+        if sequence.len != N {
+            panic!("N doesn't match the length of the init sequence");
+        }
+
+        sequence.bytes
+    }
+
+    /// Encode the init sequence into `N` bytes, counting the bytes that don't fit.
+    const fn build_sequence<SIZE: DisplaySize, const N: usize>(
+        &self,
+        controller: Controller,
+        orientation: Orientation,
+        mode: AddrMode,
+    ) -> Sequence<N> {
+        if self.validate(controller).is_err() {
+            panic!("invalid Ssd1306Config");
+        }
+
+        let configure = match SIZE::CONFIGURE_COMMANDS {
+            Some(commands) => commands,
+            None => panic!("display size is only configured at runtime"),
+        };
+        let multiplex = match multiplex(
+            controller,
+            (SIZE::WIDTH, SIZE::HEIGHT),
+            (SIZE::OFFSETX, SIZE::OFFSETY),
+            SIZE::DRIVER_ROWS,
+            SIZE::HEIGHT,
+        ) {
+            Ok(multiplex) => multiplex,
+            Err(_) => panic!("display size doesn't fit in display RAM"),
+        };
+        let tail = match self.tail_commands(controller, mode) {
+            Ok(tail) => tail,
+            Err(_) => panic!("controller only supports page addressing"),
+        };
+
+        let head = self.head_commands(controller, orientation, multiplex);
+        let mut seq = Sequence::new(controller).commands(&head);
+
+        let mut i = 0;
+        while i < configure.len() {
+            seq = seq.command(configure[i]);
+            i += 1;
+        }
+
+        seq = seq.commands(&tail);
+
+        let mut i = 0;
+        while i < self.raw_len {
+            let (data, len) = self.raw_commands[i];
+            seq = seq.bytes(&data, len);
+            i += 1;
+        }
+
+        seq.command(Command::DisplayOn(true))
+    }
+
+    /// Collect the init sequence for a display of the given size, so it can be sent in as few
+    /// transactions as possible. The commands are the ones
+    /// [`build_sequence`](Self::build_sequence) encodes, with the size's configuration
+    /// commands sent at runtime.
+    pub(crate) fn init_batch<SIZE: DisplaySize>(
+        &self,
+        size: &SIZE,
+        controller: Controller,
+        orientation: Orientation,
+        mode: AddrMode,
+    ) -> Result<CommandBatch<INIT_STREAMS>, DisplayError> {
+        self.validate(controller)?;
+
+        let multiplex = multiplex(
+            controller,
+            (size.width(), size.height()),
+            (size.offset_x(), size.offset_y()),
+            SIZE::DRIVER_ROWS,
+            size.multiplex(),
+        )?;
+        let tail = self.tail_commands(controller, mode)?;
+
+        let mut batch = CommandBatch::new();
+
+        self.head_commands(controller, orientation, multiplex)
+            .iter()
+            .try_for_each(|command| batch.push_checked(command, controller))?;
+
+        size.configure(&mut CheckedInterface {
+            interface: &mut batch,
+            controller,
+        })?;
+
+        tail.iter()
+            .try_for_each(|command| batch.push_checked(command, controller))?;
+
+        self.raw_commands()
+            .try_for_each(|raw| batch.send_commands(U8(raw)))?;
+
+        batch.push(Command::DisplayOn(true))?;
+
+        Ok(batch)
+    }
+
+    /// The init commands sent before the display size's configuration commands.
+    const fn head_commands(
+        &self,
+        controller: Controller,
+        orientation: Orientation,
+        multiplex: u8,
+    ) -> CommandList<10> {
+        let (fosc, div) = self.clock;
+        let (remap, reverse_com_dir) = orientation.remap_and_com_dir();
+
+        let mut list = CommandList::new()
+            .push(Command::DisplayOn(false))
+            .push(Command::DisplayClockDiv(fosc, div))
+            .push(Command::Multiplex(multiplex))
+            .push(Command::DisplayOffset(self.display_offset))
+            .push(Command::StartLine(self.start_line));
+
+        if controller.supports(Command::ChargePump(true)) {
+            list = list.push(Command::ChargePump(self.charge_pump()));
+        }
+
+        list.push(Command::Invert(false))
+            .push(Command::AllOn(false))
+            .push(Command::SegmentRemap(remap))
+            .push(Command::ReverseComDir(reverse_com_dir))
+    }
+
+    /// The init commands sent after the display size's configuration commands and before the raw
+    /// commands. Returns [`DisplayError::InvalidFormatError`] if the controller doesn't support
+    /// the addressing mode.
+    const fn tail_commands(
+        &self,
+        controller: Controller,
+        mode: AddrMode,
+    ) -> Result<CommandList<6>, DisplayError> {
+        let mut list = CommandList::new();
+
+        if let Some((alternative, remap)) = controller.com_pin_config() {
+            list = list.push(Command::ComPinConfig(alternative, remap));
+        }
+        if let Some((enabled, high_current)) = self.internal_iref {
+            list = list.push(Command::InternalIref(enabled, high_current));
+        }
+
+        list = list
+            .push(Command::PreChargePeriod(
+                self.precharge_phase1(controller),
+                self.brightness.precharge,
+            ))
+            .push(Command::Contrast(self.brightness.contrast))
+            .push(Command::VcomhDeselect(self.vcomh(controller)));
+
+        if controller.supports_hv_addressing() {
+            Ok(list.push(Command::AddressMode(mode)))
+        } else if matches!(mode, AddrMode::Page) {
+            Ok(list)
+        } else {
+            Err(DisplayError::InvalidFormatError)
+        }
+    }
+
+    pub(crate) const fn brightness(&self) -> Brightness {
+        self.brightness
+    }
+
+    pub(crate) const fn dim_on_sleep(&self) -> bool {
+        self.dim_on_sleep
+    }
+
+    /// Whether the charge pump should be turned on, if the controller has one.
+    pub(crate) const fn charge_pump(&self) -> bool {
+        !self.external_vcc
+    }
+
+    pub(crate) const fn vcomh(&self, controller: Controller) -> VcomhLevel {
+        match self.vcomh {
+            Some(vcomh) => vcomh,
+            None => controller.vcomh_level(),
+        }
+    }
+
+    pub(crate) const fn precharge_phase1(&self, controller: Controller) -> u8 {
+        match self.precharge_phase1 {
+            Some(phase1) => phase1,
+            None if self.external_vcc => 2,
+            None => controller.precharge_phase1(),
+        }
+    }
+
+    /// The raw commands to send at the end of initialisation.
+    pub(crate) fn raw_commands(&self) -> impl Iterator<Item = &[u8]> {
+        self.raw_commands[..self.raw_len]
+            .iter()
+            .map(|(data, len)| &data[..*len])
+    }
+}
+
+/// Check that a panel of `(width, height)` pixels at `(offset_x, offset_y)` fits in display RAM
+/// and return the multiplex ratio for driving `driven_rows` of the controller's rows.
+const fn multiplex(
+    controller: Controller,
+    (width, height): (u8, u8),
+    (offset_x, offset_y): (u8, u8),
+    driver_rows: u8,
+    driven_rows: u8,
+) -> Result<u8, DisplayError> {
+    let columns = controller.column_offset() as u16 + offset_x as u16 + width as u16;
+    let rows = offset_y as u16 + height as u16;
+
+    if height == 0
+        || driven_rows == 0
+        || driven_rows > driver_rows
+        || columns > controller.ram_columns() as u16
+        || rows > driver_rows as u16
+    {
+        Err(DisplayError::OutOfBoundsError)
+    } else {
+        Ok(driven_rows - 1)
+    }
+}
+
+/// Up to `N` commands of the init sequence, collected in a `const fn` so the encoded sequence
+/// and the batch sent at runtime are built from the same list.
+#[derive(Debug, Copy, Clone)]
+struct CommandList<const N: usize> {
+    commands: [Option<Command>; N],
+    len: usize,
+}
+
+impl<const N: usize> CommandList<N> {
+    const fn new() -> Self {
+        Self {
+            commands: [None; N],
+            len: 0,
+        }
+    }
+
+    const fn push(mut self, command: Command) -> Self {
+        self.commands[self.len] = Some(command);
+        self.len += 1;
+
+        self
+    }
+
+    fn iter(&self) -> impl Iterator<Item = Command> + '_ {
+        self.commands.iter().flatten().copied()
+    }
+}
+
+/// Init sequence being encoded at compile time.
+struct Sequence<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    controller: Controller,
+}
+
+impl<const N: usize> Sequence<N> {
+    const fn new(controller: Controller) -> Self {
+        Self {
+            bytes: [0; N],
+            len: 0,
+            controller,
+        }
+    }
+
+    const fn command(self, command: Command) -> Self {
+        if !self.controller.supports(command) {
+            panic!("command not supported by the controller");
+        }
+
+        let (data, len) = command.encode();
+        self.bytes(&data, len)
+    }
+
+    const fn commands<const M: usize>(mut self, list: &CommandList<M>) -> Self {
+        let mut i = 0;
+        while i < list.len {
+            if let Some(command) = list.commands[i] {
+                self = self.command(command);
+            }
+            i += 1;
+        }
+
+        self
+    }
+
+    /// Append the first `len` bytes of `data`. Bytes past the end of the buffer are only counted.
+    const fn bytes(mut self, data: &[u8], len: usize) -> Self {
+        let mut i = 0;
+        while i < len {
+            if self.len < N {
+                self.bytes[self.len] = data[i];
+            }
+            self.len += 1;
+            i += 1;
+        }
+
+        self
+    }
+}
@@ -0,0 +1,159 @@
+//! Display controller.
+
+use crate::command::{AddrMode, Command, VcomhLevel};
+use display_interface::DisplayError;
+
+/// Display controller driving the panel.
+///
+/// Panels sold as "SSD1306 compatible" are often driven by a different chip. The controller
+/// determines the size of display RAM, where the panel sits in it, parts of the initialisation
+/// sequence and which commands can be sent.
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use ssd1306::{controller::Controller, prelude::*, Ssd1306};
+///
+/// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .with_controller(Controller::Sh1106)
+///     .into_buffered_graphics_mode()
+///     .init()
+///     .unwrap();
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Controller {
+    /// Solomon Systech SSD1306, 128 x 64 display RAM
+    #[default]
+    Ssd1306,
+    /// Sino Wealth SH1106, 132 x 64 display RAM with the panel centered in it.
+    ///
+    /// The SH1106 only supports page addressing mode and has no charge pump or scrolling
+    /// commands.
+    Sh1106,
+    /// Solomon Systech SSD1305, 132 x 64 display RAM with the panel starting at column 4.
+    ///
+    /// The SSD1305 needs an external VCC supply and has no charge pump.
+    Ssd1305,
+    /// Solomon Systech SSD1309, 128 x 64 display RAM, as used by most 2.42" panels.
+    ///
+    /// The SSD1309 needs an external VCC supply and has no charge pump.
+    Ssd1309,
+    /// Solomon Systech SSD1312, 128 x 64 display RAM
+    Ssd1312,
+}
+
+impl Controller {
+    /// Display RAM column of the first panel column, before the display size's
+    /// [`OFFSETX`](crate::size::DisplaySize::OFFSETX) is added.
+    ///
+    /// This applies to the unrotated display. When segment remapping is flipped the offset is
+    /// counted from the other end of display RAM instead.
+    ///
+    /// ```rust
+    /// use ssd1306::{controller::Controller, emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// for rotation in [DisplayRotation::Rotate0, DisplayRotation::Rotate180] {
+    ///     let mut emulator = Emulator::new(DisplaySize128x32).with_controller(Controller::Ssd1305);
+    ///
+    ///     let mut display = Ssd1306::new(&mut emulator, DisplaySize128x32, rotation)
+    ///         .with_controller(Controller::Ssd1305)
+    ///         .into_buffered_graphics_mode()
+    ///         .init()
+    ///         .unwrap();
+    ///     display.set_pixel(0, 0, true);
+    ///     display.flush().unwrap();
+    ///     drop(display);
+    ///
+    ///     let (x, y) = match rotation {
+    ///         DisplayRotation::Rotate0 => (0, 0),
+    ///         _ => (127, 31),
+    ///     };
+    ///     assert!(emulator.panel_pixel(x, y));
+    /// }
+    ///
+    /// assert_eq!(Controller::Ssd1305.column_offset(), 4);
+    /// ```
+    pub const fn column_offset(self) -> u8 {
+        match self {
+            Controller::Ssd1306 | Controller::Ssd1309 | Controller::Ssd1312 => 0,
+            Controller::Sh1106 => 2,
+            Controller::Ssd1305 => 4,
+        }
+    }
+
+    /// Number of columns in display RAM.
+    pub const fn ram_columns(self) -> u8 {
+        match self {
+            Controller::Ssd1306 | Controller::Ssd1309 | Controller::Ssd1312 => 128,
+            Controller::Sh1106 | Controller::Ssd1305 => 132,
+        }
+    }
+
+    /// Whether the horizontal and vertical addressing modes are supported. If not, data is sent
+    /// one page at a time in page addressing mode.
+    pub const fn supports_hv_addressing(self) -> bool {
+        !matches!(self, Controller::Sh1106)
+    }
+
+    /// Addressing mode used by display modes that write whole areas of display RAM at once.
+    pub(crate) const fn area_addr_mode(self) -> AddrMode {
+        if self.supports_hv_addressing() {
+            AddrMode::Horizontal
+        } else {
+            AddrMode::Page
+        }
+    }
+
+    /// COM pin configuration required by the controller, overriding the one sent by the display
+    /// size. `None` if the display size decides.
+    pub(crate) const fn com_pin_config(self) -> Option<(bool, bool)> {
+        match self {
+            Controller::Ssd1306 | Controller::Sh1106 => None,
+            Controller::Ssd1305 | Controller::Ssd1309 | Controller::Ssd1312 => Some((true, false)),
+        }
+    }
+
+    /// Phase 1 length of the precharge period sent along with a
+    /// [`Brightness`](crate::prelude::Brightness).
+    pub(crate) const fn precharge_phase1(self) -> u8 {
+        match self {
+            Controller::Ssd1306 => 1,
+            // Reset default of the other controllers
+            _ => 2,
+        }
+    }
+
+    /// VCOMH deselect level sent during initialisation.
+    pub(crate) const fn vcomh_level(self) -> VcomhLevel {
+        match self {
+            Controller::Ssd1305 | Controller::Ssd1309 => VcomhLevel::V077,
+            _ => VcomhLevel::Auto,
+        }
+    }
+
+    /// Whether the controller understands the given command.
+    pub const fn supports(self, command: Command) -> bool {
+        match command {
+            Command::HScrollSetup(..)
+            | Command::VHScrollSetup(..)
+            | Command::EnableScroll(_)
+            | Command::VScrollArea(..) => !matches!(self, Controller::Sh1106),
+            Command::AddressMode(_)
+            | Command::ColumnAddress(..)
+            | Command::PageAddress(..)
+            | Command::PageAddressNum(..) => self.supports_hv_addressing(),
+            Command::ChargePump(_) => matches!(self, Controller::Ssd1306 | Controller::Ssd1312),
+            // 0xAD is a different command on the SH1106 and SSD1305
+            Command::InternalIref(..) => matches!(self, Controller::Ssd1306 | Controller::Ssd1312),
+            _ => true,
+        }
+    }
+
+    /// Check that every command in an encoded command sequence is supported.
+    pub(crate) fn check_encoded(self, bytes: &[u8]) -> Result<(), DisplayError> {
+        Command::decode_all(bytes).try_for_each(|command| match command {
+            Ok(command) if self.supports(command) => Ok(()),
+            _ => Err(DisplayError::InvalidFormatError),
+        })
+    }
+}
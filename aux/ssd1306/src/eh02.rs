@@ -0,0 +1,311 @@
+//! Support for embedded-hal 0.2 peripherals.
+//!
+//! Available with the `embedded-hal-02` feature. HALs that still implement the 0.2 traits can
+//! drive the display through the interfaces in this module without any adapter code. They
+//! implement [`WriteOnlyDataCommand`] and so work with every display mode of
+//! [`Ssd1306`].
+//!
+//! ```rust
+//! # use ssd1306::test_helpers::I2cStub02;
+//! # let i2c = I2cStub02;
+//! use ssd1306::{eh02::I2CDisplayInterface, prelude::*, Ssd1306};
+//!
+//! let interface = I2CDisplayInterface::new(i2c);
+//! let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+//!     .into_buffered_graphics_mode()
+//!     .init()
+//!     .unwrap();
+//! ```
+
+use crate::{error::Error, Ssd1306};
+use core::convert::Infallible;
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+use embedded_hal_02::{
+    blocking::{delay::DelayMs, i2c, spi},
+    digital::v2::OutputPin,
+};
+
+/// Helper struct to create preconfigured embedded-hal 0.2 I2C interfaces for the display.
+#[derive(Debug, Copy, Clone)]
+pub struct I2CDisplayInterface(());
+
+impl I2CDisplayInterface {
+    /// Create new builder with a default I2C address of 0x3C
+    pub fn new<I>(i2c: I) -> I2CInterface<I>
+    where
+        I: i2c::Write,
+    {
+        Self::new_custom_address(i2c, 0x3C)
+    }
+
+    /// Create a new I2C interface with the alternate address 0x3D as specified in the datasheet.
+    pub fn new_alternate_address<I>(i2c: I) -> I2CInterface<I>
+    where
+        I: i2c::Write,
+    {
+        Self::new_custom_address(i2c, 0x3D)
+    }
+
+    /// Create a new I2C interface with a custom address.
+    pub fn new_custom_address<I>(i2c: I, address: u8) -> I2CInterface<I>
+    where
+        I: i2c::Write,
+    {
+        I2CInterface::new(i2c, address, 0x40)
+    }
+}
+
+/// I2C display interface for embedded-hal 0.2 buses.
+#[derive(Debug, Copy, Clone)]
+pub struct I2CInterface<I2C> {
+    i2c: I2C,
+    addr: u8,
+    data_byte: u8,
+}
+
+impl<I2C> I2CInterface<I2C>
+where
+    I2C: i2c::Write,
+{
+    /// Create a new I2C interface with the given address and data mode control byte.
+    pub fn new(i2c: I2C, addr: u8, data_byte: u8) -> Self {
+        Self {
+            i2c,
+            addr,
+            data_byte,
+        }
+    }
+
+    /// Consume the interface and return the underlying I2C peripheral.
+    pub fn release(self) -> I2C {
+        self.i2c
+    }
+}
+
+impl<I2C> WriteOnlyDataCommand for I2CInterface<I2C>
+where
+    I2C: i2c::Write,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmds {
+            DataFormat::U8(slice) => {
+                // Copy over given commands to new array to prefix with command identifier
+                let mut writebuf: [u8; 8] = [0; 8];
+                let writebuf = writebuf
+                    .get_mut(..=slice.len())
+                    .ok_or(DisplayError::OutOfBoundsError)?;
+                writebuf[1..].copy_from_slice(slice);
+
+                self.i2c
+                    .write(self.addr, writebuf)
+                    .map_err(|_| DisplayError::BusWriteError)
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        let mut writebuf = [0; 17];
+
+        // Data mode
+        writebuf[0] = self.data_byte;
+
+        match buf {
+            DataFormat::U8(slice) => slice.chunks(16).try_for_each(|chunk| {
+                let chunk_len = chunk.len();
+
+                // Copy over all data from buffer, leaving the data command byte intact
+                writebuf[1..=chunk_len].copy_from_slice(chunk);
+
+                self.i2c
+                    .write(self.addr, &writebuf[..=chunk_len])
+                    .map_err(|_| DisplayError::BusWriteError)
+            }),
+            DataFormat::U8Iter(iter) => {
+                let mut i = 1;
+
+                for byte in iter {
+                    writebuf[i] = byte;
+                    i += 1;
+
+                    if i == writebuf.len() {
+                        self.i2c
+                            .write(self.addr, &writebuf)
+                            .map_err(|_| DisplayError::BusWriteError)?;
+                        i = 1;
+                    }
+                }
+
+                if i > 1 {
+                    self.i2c
+                        .write(self.addr, &writebuf[..i])
+                        .map_err(|_| DisplayError::BusWriteError)?;
+                }
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+/// SPI display interface for embedded-hal 0.2 buses with a manually driven CS pin.
+#[derive(Debug, Copy, Clone)]
+pub struct SPIInterface<SPI, DC, CS> {
+    spi_no_cs: SPIInterfaceNoCS<SPI, DC>,
+    cs: CS,
+}
+
+impl<SPI, DC, CS> SPIInterface<SPI, DC, CS>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    /// Create a new SPI interface using the given data/command and chip select pins.
+    pub fn new(spi: SPI, dc: DC, cs: CS) -> Self {
+        Self {
+            spi_no_cs: SPIInterfaceNoCS::new(spi, dc),
+            cs,
+        }
+    }
+
+    /// Consume the interface and return the underlying SPI peripheral, D/C and CS pins.
+    pub fn release(self) -> (SPI, DC, CS) {
+        let (spi, dc) = self.spi_no_cs.release();
+        (spi, dc, self.cs)
+    }
+
+    fn with_cs(
+        &mut self,
+        f: impl FnOnce(&mut SPIInterfaceNoCS<SPI, DC>) -> Result<(), DisplayError>,
+    ) -> Result<(), DisplayError> {
+        // Assert chip select pin
+        self.cs.set_low().map_err(|_| DisplayError::CSError)?;
+
+        let result = f(&mut self.spi_no_cs);
+
+        // Deassert chip select pin
+        self.cs.set_high().ok();
+
+        result
+    }
+}
+
+impl<SPI, DC, CS> WriteOnlyDataCommand for SPIInterface<SPI, DC, CS>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+    CS: OutputPin,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.with_cs(|iface| iface.send_commands(cmds))
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.with_cs(|iface| iface.send_data(buf))
+    }
+}
+
+/// SPI display interface for embedded-hal 0.2 buses where CS is tied low or handled elsewhere.
+#[derive(Debug, Copy, Clone)]
+pub struct SPIInterfaceNoCS<SPI, DC> {
+    spi: SPI,
+    dc: DC,
+}
+
+impl<SPI, DC> SPIInterfaceNoCS<SPI, DC>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+{
+    /// Create a new SPI interface using the given data/command pin.
+    pub fn new(spi: SPI, dc: DC) -> Self {
+        Self { spi, dc }
+    }
+
+    /// Consume the interface and return the underlying SPI peripheral and D/C pin.
+    pub fn release(self) -> (SPI, DC) {
+        (self.spi, self.dc)
+    }
+
+    fn send(&mut self, data: DataFormat<'_>) -> Result<(), DisplayError> {
+        match data {
+            DataFormat::U8(slice) => self
+                .spi
+                .write(slice)
+                .map_err(|_| DisplayError::BusWriteError),
+            DataFormat::U8Iter(iter) => {
+                let mut buf = [0; 32];
+                let mut i = 0;
+
+                for byte in iter {
+                    buf[i] = byte;
+                    i += 1;
+
+                    if i == buf.len() {
+                        self.spi
+                            .write(&buf)
+                            .map_err(|_| DisplayError::BusWriteError)?;
+                        i = 0;
+                    }
+                }
+
+                if i > 0 {
+                    self.spi
+                        .write(&buf[..i])
+                        .map_err(|_| DisplayError::BusWriteError)?;
+                }
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl<SPI, DC> WriteOnlyDataCommand for SPIInterfaceNoCS<SPI, DC>
+where
+    SPI: spi::Write<u8>,
+    DC: OutputPin,
+{
+    fn send_commands(&mut self, cmds: DataFormat<'_>) -> Result<(), DisplayError> {
+        // 1 = data, 0 = command
+        self.dc.set_low().map_err(|_| DisplayError::DCError)?;
+        self.send(cmds)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        // 1 = data, 0 = command
+        self.dc.set_high().map_err(|_| DisplayError::DCError)?;
+        self.send(buf)
+    }
+}
+
+// SPI-only reset
+impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE> {
+    /// Reset the display using an embedded-hal 0.2 reset pin and delay.
+    pub fn reset_eh02<RST, DELAY>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<Infallible, RST::Error>>
+    where
+        RST: OutputPin,
+        DELAY: DelayMs<u8>,
+    {
+        fn inner_reset<RST, DELAY>(rst: &mut RST, delay: &mut DELAY) -> Result<(), RST::Error>
+        where
+            RST: OutputPin,
+            DELAY: DelayMs<u8>,
+        {
+            rst.set_high()?;
+            delay.delay_ms(1);
+            rst.set_low()?;
+            delay.delay_ms(10);
+            rst.set_high()
+        }
+
+        inner_reset(rst, delay).map_err(Error::Pin)
+    }
+}
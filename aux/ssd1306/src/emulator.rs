@@ -0,0 +1,384 @@
+//! Host-side SSD1306 controller emulator.
+//!
+//! [`Emulator`] implements [`WriteOnlyDataCommand`] and interprets the command and data bytes sent
+//! to it the same way the SSD1306 does: it keeps the full 128x64 GDDRAM, the address pointers for
+//! all three addressing modes and the registers that affect how GDDRAM is mapped onto the panel.
+//! Other [`Controller`]s are emulated too, including their wider RAM and the commands they reject.
+//! This makes it possible to check what pixels actually light up on a panel without any hardware.
+//! Command bytes are decoded with [`Command::decode_all`]. Byte sequences the decoder rejects and
+//! commands the emulated controller doesn't support both fail with
+//! [`DisplayError::InvalidFormatError`].
+//!
+//! The emulator is passed to the driver by mutable reference so it can be inspected once the
+//! driver is dropped. With the `async` feature enabled it can also be used with
+//! [`Ssd1306Async`](crate::Ssd1306Async).
+//!
+//! ```rust
+//! use ssd1306::{emulator::Emulator, prelude::*, Ssd1306};
+//!
+//! let mut emulator = Emulator::new(DisplaySize64x48);
+//!
+//! let mut display = Ssd1306::new(&mut emulator, DisplaySize64x48, DisplayRotation::Rotate0)
+//!     .into_buffered_graphics_mode()
+//!     .init()
+//!     .unwrap();
+//! display.set_pixel(0, 0, true);
+//! display.flush().unwrap();
+//! drop(display);
+//!
+//! // The 64x48 panel starts 32 columns into GDDRAM
+//! assert!(emulator.ram_pixel(32, 0));
+//! assert!(emulator.panel_pixel(0, 0));
+//! assert!(!emulator.panel_pixel(1, 0));
+//! ```
+
+#[cfg(feature = "async")]
+use crate::asynch::AsyncWriteOnlyDataCommand;
+use crate::{
+    command::{AddrMode, Command},
+    controller::Controller,
+    size::DisplaySize,
+};
+use display_interface::{DataFormat, DisplayError, WriteOnlyDataCommand};
+
+/// Number of columns in the widest supported GDDRAM
+const RAM_COLS: usize = 132;
+
+/// Number of 8px pages in the SSD1306 GDDRAM
+const RAM_PAGES: usize = 8;
+
+/// Emulated SSD1306 controller connected to a panel of a given size.
+///
+/// See the [module level documentation](self) for an example.
+#[derive(Copy, Clone, Debug)]
+pub struct Emulator {
+    gddram: [[u8; RAM_COLS]; RAM_PAGES],
+
+    addr_mode: AddrMode,
+    col: u8,
+    page: u8,
+    col_start: u8,
+    col_end: u8,
+    page_start: u8,
+    page_end: u8,
+
+    segment_remap: bool,
+    reverse_com_dir: bool,
+    start_line: u8,
+    display_offset: u8,
+    multiplex: u8,
+    inverted: bool,
+    all_on: bool,
+    display_on: bool,
+    charge_pump: bool,
+    contrast: u8,
+
+    panel_width: u8,
+    panel_height: u8,
+    panel_offset_x: u8,
+    controller: Controller,
+}
+
+impl Emulator {
+    /// Create a new emulator in the controller's power-on reset state, attached to a panel of the
+    /// given size.
+    ///
+    /// The panel is assumed to be wired the way the driver expects for
+    /// [`DisplayRotation::Rotate0`](crate::rotation::DisplayRotation::Rotate0), i.e. with segment
+    /// remapping and reversed COM scan direction the top left pixel shows GDDRAM column
+    /// [`offset_x`](DisplaySize::offset_x), row 0.
+    pub fn new<SIZE: DisplaySize>(size: SIZE) -> Self {
+        Self {
+            gddram: [[0; RAM_COLS]; RAM_PAGES],
+            addr_mode: AddrMode::Page,
+            col: 0,
+            page: 0,
+            col_start: 0,
+            col_end: Controller::Ssd1306.ram_columns() - 1,
+            page_start: 0,
+            page_end: RAM_PAGES as u8 - 1,
+            segment_remap: false,
+            reverse_com_dir: false,
+            start_line: 0,
+            display_offset: 0,
+            multiplex: 63,
+            inverted: false,
+            all_on: false,
+            display_on: false,
+            charge_pump: false,
+            contrast: 0x7F,
+            panel_width: size.width(),
+            panel_height: size.height(),
+            panel_offset_x: size.offset_x(),
+            controller: Controller::Ssd1306,
+        }
+    }
+
+    /// Emulate a different controller. The default is [`Controller::Ssd1306`].
+    ///
+    /// Commands the controller doesn't support are rejected with
+    /// [`DisplayError::InvalidFormatError`].
+    ///
+    /// ```rust
+    /// use ssd1306::{controller::Controller, emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64).with_controller(Controller::Sh1106);
+    ///
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_controller(Controller::Sh1106)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.set_pixel(0, 10, true);
+    /// display.set_pixel(127, 63, true);
+    /// display.flush().unwrap();
+    /// drop(display);
+    ///
+    /// assert!(emulator.panel_pixel(0, 10));
+    /// assert!(emulator.panel_pixel(127, 63));
+    /// assert!(!emulator.panel_pixel(1, 10));
+    /// ```
+    pub fn with_controller(mut self, controller: Controller) -> Self {
+        self.controller = controller;
+        self.col_end = controller.ram_columns() - 1;
+        self
+    }
+
+    /// Get the raw GDDRAM contents, one array of column bytes per page.
+    ///
+    /// The array is wide enough for every supported controller; columns beyond the emulated
+    /// controller's RAM are always zero.
+    pub fn gddram(&self) -> &[[u8; RAM_COLS]; RAM_PAGES] {
+        &self.gddram
+    }
+
+    /// Get the value of a single bit in GDDRAM. `row` is in pixels, i.e. `0..64`.
+    ///
+    /// Out of range coordinates return `false`.
+    pub fn ram_pixel(&self, col: u8, row: u8) -> bool {
+        self.gddram
+            .get(row as usize / 8)
+            .and_then(|page| page.get(col as usize))
+            .is_some_and(|byte| byte & (1 << (row % 8)) != 0)
+    }
+
+    /// Get the value of a pixel as it appears on the attached panel.
+    ///
+    /// This takes into account the panel offset, segment remapping, COM scan direction, display
+    /// start line, display offset, multiplex ratio, inversion and whether the display is on.
+    /// Out of range coordinates return `false`.
+    pub fn panel_pixel(&self, x: u8, y: u8) -> bool {
+        if x >= self.panel_width || y >= self.panel_height || !self.display_on {
+            return false;
+        }
+
+        if self.all_on {
+            return true;
+        }
+
+        let ram_cols = self.controller.ram_columns();
+        let seg = ram_cols - 1 - self.controller.column_offset() - self.panel_offset_x - x;
+        let com = self.panel_height - 1 - y;
+
+        // COM outputs outside of the multiplex ratio are not driven
+        let mux = self.multiplex + 1;
+        if com >= mux {
+            return false;
+        }
+
+        let row = if self.reverse_com_dir { mux - 1 - com } else { com };
+        let row = (row + self.start_line + self.display_offset) % 64;
+        let col = if self.segment_remap {
+            ram_cols - 1 - seg
+        } else {
+            seg
+        };
+
+        self.ram_pixel(col, row) != self.inverted
+    }
+
+    /// Panel dimensions in pixels as `(width, height)`.
+    pub fn panel_size(&self) -> (u8, u8) {
+        (self.panel_width, self.panel_height)
+    }
+
+    /// Whether the display is currently switched on.
+    pub fn is_display_on(&self) -> bool {
+        self.display_on
+    }
+
+    /// Whether the charge pump is currently enabled.
+    pub fn is_charge_pump_on(&self) -> bool {
+        self.charge_pump
+    }
+
+    /// Whether the display is currently inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Current contrast register value.
+    pub fn contrast(&self) -> u8 {
+        self.contrast
+    }
+
+    /// Current addressing mode.
+    pub fn addr_mode(&self) -> AddrMode {
+        self.addr_mode
+    }
+
+    /// Current `(column, page)` address pointer.
+    pub fn address(&self) -> (u8, u8) {
+        (self.col, self.page)
+    }
+
+    fn write_data(&mut self, byte: u8) {
+        if self.col < self.controller.ram_columns() {
+            self.gddram[self.page as usize][self.col as usize] = byte;
+        }
+
+        match self.addr_mode {
+            AddrMode::Page => {
+                self.col = if self.col >= self.col_end {
+                    self.col_start
+                } else {
+                    self.col + 1
+                };
+            }
+            AddrMode::Horizontal => {
+                if self.col >= self.col_end {
+                    self.col = self.col_start;
+                    self.page = if self.page >= self.page_end {
+                        self.page_start
+                    } else {
+                        self.page + 1
+                    };
+                } else {
+                    self.col += 1;
+                }
+            }
+            AddrMode::Vertical => {
+                if self.page >= self.page_end {
+                    self.page = self.page_start;
+                    self.col = if self.col >= self.col_end {
+                        self.col_start
+                    } else {
+                        self.col + 1
+                    };
+                } else {
+                    self.page += 1;
+                }
+            }
+        }
+    }
+
+    /// Apply a decoded command to the emulated registers.
+    fn execute(&mut self, command: Command) -> Result<(), DisplayError> {
+        if !self.controller.supports(command) {
+            return Err(DisplayError::InvalidFormatError);
+        }
+
+        // The SSD1306 ignores the top bit of column addresses
+        let (upper_col_mask, col_mask) = if self.controller.ram_columns() > 128 {
+            (0x0F, 0xFF)
+        } else {
+            (0x07, 0x7F)
+        };
+
+        match command {
+            Command::Contrast(contrast) => self.contrast = contrast,
+            Command::AllOn(on) => self.all_on = on,
+            Command::Invert(inverted) => self.inverted = inverted,
+            Command::DisplayOn(on) => self.display_on = on,
+            Command::ChargePump(on) => self.charge_pump = on,
+            Command::LowerColStart(nibble) => self.col = (self.col & 0xF0) | (nibble & 0x0F),
+            Command::UpperColStart(nibble) => {
+                self.col = ((nibble & upper_col_mask) << 4) | (self.col & 0x0F)
+            }
+            Command::AddressMode(mode) => self.addr_mode = mode,
+            Command::ColumnAddress(start, end) => {
+                self.col_start = start & col_mask;
+                self.col_end = end & col_mask;
+                self.col = self.col_start;
+            }
+            Command::PageAddressNum(start, end) => {
+                self.page_start = start & 0x07;
+                self.page_end = end & 0x07;
+                self.page = self.page_start;
+            }
+            Command::PageStart(page) => self.page = page as u8 & 0x07,
+            Command::StartLine(line) => self.start_line = line & 0x3F,
+            Command::SegmentRemap(remap) => self.segment_remap = remap,
+            Command::Multiplex(ratio) => self.multiplex = ratio & 0x3F,
+            Command::ReverseComDir(reverse) => self.reverse_com_dir = reverse,
+            Command::DisplayOffset(offset) => self.display_offset = offset & 0x3F,
+            // Scrolling, timing and analog settings don't affect GDDRAM or its mapping
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl WriteOnlyDataCommand for Emulator {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        match cmd {
+            DataFormat::U8(bytes) => {
+                Command::decode_all(bytes).try_for_each(|command| {
+                    command
+                        .map_err(|_| DisplayError::InvalidFormatError)
+                        .and_then(|command| self.execute(command))
+                })
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            DataFormat::U8(bytes) => {
+                bytes.iter().for_each(|byte| self.write_data(*byte));
+
+                Ok(())
+            }
+            DataFormat::U8Iter(iter) => {
+                iter.for_each(|byte| self.write_data(byte));
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl WriteOnlyDataCommand for &mut Emulator {
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_commands(*self, cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_data(*self, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncWriteOnlyDataCommand for Emulator {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_commands(self, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_data(self, buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncWriteOnlyDataCommand for &mut Emulator {
+    async fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_commands(*self, cmd)
+    }
+
+    async fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        WriteOnlyDataCommand::send_data(*self, buf)
+    }
+}
@@ -1,8 +1,147 @@
+//! Driver errors.
+
+use core::convert::Infallible;
+use display_interface::DisplayError;
+
 /// Errors in this crate
+///
+/// Methods that check their arguments return this error instead of panicking on bad input, so
+/// firmware built with a halting panic handler can't freeze in the driver. The type parameters
+/// are only used by methods that drive pins themselves, such as
+/// [`reset`](crate::Ssd1306::reset).
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// # let interface = StubInterface;
+/// use ssd1306::{prelude::*, Error, Ssd1306};
+///
+/// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode()
+///     .init()
+///     .unwrap();
+///
+/// // The end of an area is exclusive, so this area is empty
+/// assert!(matches!(display.set_draw_area((0, 0), (0, 0)), Err(Error::InvalidArea)));
+/// // Display RAM has 64 rows, so the last row is 63
+/// assert!(matches!(display.set_row(64), Err(Error::OutOfBounds)));
+/// ```
+///
+/// No driver method panics, whatever arguments it is given:
+///
+/// ```rust
+/// # use ssd1306::test_helpers::StubInterface;
+/// use core::fmt::Write;
+/// use ssd1306::{command::AddrMode, controller::Controller, prelude::*, size::DisplaySizeDynamic};
+/// use ssd1306::Ssd1306;
+///
+/// const VALUES: [u8; 8] = [0, 1, 7, 8, 63, 127, 128, 255];
+///
+/// let sizes = [
+///     DisplaySizeDynamic::new(128, 64).unwrap(),
+///     DisplaySizeDynamic::new(4, 4).unwrap(),
+///     // Only fits the SH1106
+///     DisplaySizeDynamic::new(8, 8).and_then(|size| size.with_offset(122, 56)).unwrap(),
+/// ];
+/// let rotations = [DisplayRotation::Rotate0, DisplayRotation::Rotate90];
+/// let controllers = [
+///     Controller::Ssd1306,
+///     Controller::Sh1106,
+///     Controller::Ssd1305,
+///     Controller::Ssd1309,
+///     Controller::Ssd1312,
+/// ];
+/// let modes = [AddrMode::Page, AddrMode::Horizontal, AddrMode::Vertical];
+/// let buffer = [0xFF; 1024];
+///
+/// for controller in controllers {
+///     for (size, rotation) in sizes.iter().flat_map(|s| rotations.iter().map(move |r| (*s, *r))) {
+///         for mode in modes {
+///             let display = Ssd1306::new(StubInterface, size, rotation);
+///             let display = display.with_controller(controller).init_with_addr_mode(mode);
+///             let mut display = match display {
+///                 Ok(display) => display,
+///                 // Sizes that don't fit in display RAM and addressing modes the controller
+///                 // lacks can't be initialised
+///                 Err(_) => continue,
+///             };
+///             let _ = display.clear();
+///
+///             for &a in &VALUES {
+///                 let _ = display.set_row(a);
+///                 let _ = display.set_column(a);
+///                 #[allow(deprecated)]
+///                 let _ = display.set_column_2(a);
+///                 for &b in &VALUES {
+///                     if let Ok(brightness) = Brightness::try_custom(a, b) {
+///                         let _ = display.set_brightness(brightness);
+///                     }
+///                     for (&c, &d) in VALUES.iter().zip(VALUES.iter().rev()) {
+///                         let _ = display.set_draw_area((a, b), (c, d));
+///                         for width in [0, 1, 128, usize::MAX] {
+///                             let _ = display.bounded_draw(&buffer, width, (a, b), (c, d));
+///                         }
+///                     }
+///                 }
+///             }
+///
+///             let mut buffered = display.into_buffered_graphics_mode();
+///             let _ = buffered.init();
+///             let _ = buffered.set_addr_mode(mode);
+///             for &a in &VALUES {
+///                 buffered.set_pixel(a.into(), u32::MAX - u32::from(a), true);
+///                 buffered.set_pixel(a.into(), a.into(), true);
+///                 let _ = buffered.flush();
+///             }
+///         }
+///
+///         let terminal = Ssd1306::new(StubInterface, size, rotation)
+///             .with_controller(controller)
+///             .into_terminal_mode();
+///         let mut terminal = match terminal.init() {
+///             Ok(terminal) => terminal,
+///             // Too small for a single character
+///             Err(_) => continue,
+///         };
+///         for &a in &VALUES {
+///             let _ = terminal.set_position(a, a);
+///         }
+///         for c in ['\n', '\r', 'a', '\u{FFFF}'] {
+///             for _ in 0..300 {
+///                 let _ = terminal.print_char(c);
+///             }
+///         }
+///         let _ = write!(terminal, "hello");
+///     }
+/// }
+/// ```
 #[derive(Debug)]
-pub enum Error<CommE, PinE> {
+pub enum Error<CommE = Infallible, PinE = Infallible> {
     /// Communication error
     Comm(CommE),
     /// Pin setting error
     Pin(PinE),
+    /// Error from the display interface
+    Interface(DisplayError),
+    /// An area is empty, its end comes before its start or it doesn't fit in display RAM
+    InvalidArea,
+    /// A coordinate or address is outside of display RAM
+    OutOfBounds,
+}
+
+impl<CommE, PinE> From<DisplayError> for Error<CommE, PinE> {
+    fn from(error: DisplayError) -> Self {
+        Error::Interface(error)
+    }
+}
+
+/// Converts errors for use in methods that return [`DisplayError`]. Invalid areas and
+/// coordinates become [`DisplayError::OutOfBoundsError`].
+impl From<Error> for DisplayError {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Comm(never) | Error::Pin(never) => match never {},
+            Error::Interface(error) => error,
+            Error::InvalidArea | Error::OutOfBounds => DisplayError::OutOfBoundsError,
+        }
+    }
 }
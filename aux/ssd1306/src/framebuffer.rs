@@ -0,0 +1,790 @@
+//! A page-packed framebuffer that can be drawn to without a display.
+
+use core::{
+    fmt,
+    ops::{Range, RangeInclusive},
+};
+
+use crate::{
+    controller::Controller,
+    rotation::{DisplayRotation, Orientation},
+    size::{self, DisplaySize, NewZeroed},
+};
+
+/// Most pages a buffer can have. Page addresses only go up to 15.
+const MAX_PAGES: usize = 16;
+
+/// Roughly how many bytes flushing another area costs in horizontal addressing mode: the column
+/// and page address commands and the extra bus transactions.
+const AREA_OVERHEAD: usize = 8;
+
+/// Column span of a page that hasn't changed.
+const CLEAN: (u8, u8) = (u8::MAX, 0);
+
+/// A page-packed pixel buffer with change tracking, matching the layout of display RAM.
+///
+/// A framebuffer doesn't own a display interface, so it can be drawn to in one place and sent to
+/// the display from another with [`Ssd1306::flush_from`](crate::Ssd1306::flush_from). It
+/// implements [`DrawTarget`](embedded_graphics_core::draw_target::DrawTarget) when the `graphics`
+/// feature is enabled, so rendering code can also be tested without a display.
+///
+/// [`BufferedGraphicsMode`](crate::mode::BufferedGraphicsMode) keeps its pixels in a framebuffer
+/// too.
+///
+/// ```rust
+/// use ssd1306::{prelude::*, Framebuffer};
+///
+/// let mut framebuffer = Framebuffer::new(DisplaySize128x64, DisplayRotation::Rotate0);
+///
+/// framebuffer.set_pixel(10, 20, true);
+///
+/// assert_eq!(framebuffer.get_pixel(10, 20), Some(true));
+/// // Page 2, column 10, row 4 of the page
+/// assert_eq!(framebuffer.buffer()[2 * 128 + 10], 0b0001_0000);
+/// ```
+///
+/// Panels whose height isn't a multiple of 8 only use part of their last page:
+///
+/// ```rust
+/// use ssd1306::{
+///     emulator::Emulator,
+///     prelude::*,
+///     size::{buffer_size, DisplaySizeCustom},
+///     Framebuffer, Ssd1306,
+/// };
+///
+/// let size = DisplaySizeCustom::<128, 30, { buffer_size(128, 30) }>::new();
+/// let mut framebuffer = Framebuffer::new(size, DisplayRotation::Rotate0);
+/// framebuffer.set_pixel(5, 29, true);
+///
+/// let mut emulator = Emulator::new(size);
+/// let mut display = Ssd1306::new(&mut emulator, size, DisplayRotation::Rotate0)
+///     .init()
+///     .unwrap();
+/// display.flush_from(&mut framebuffer).unwrap();
+/// # drop(display);
+///
+/// assert!(emulator.panel_pixel(5, 29));
+/// ```
+pub struct Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    buffer: SIZE::Buffer,
+    /// Width of the unrotated display, which is also the width of a buffer page in bytes
+    width: u8,
+    /// Height of the unrotated display
+    height: u8,
+    orientation: Orientation,
+    /// Columns changed in each buffer page since the last flush, as inclusive `(min, max)` spans
+    dirty: [(u8, u8); MAX_PAGES],
+}
+
+// Derived impls would require `SIZE` itself to implement the traits instead of its buffer
+impl<SIZE> Clone for Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+    SIZE::Buffer: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            width: self.width,
+            height: self.height,
+            orientation: self.orientation,
+            dirty: self.dirty,
+        }
+    }
+}
+
+impl<SIZE> fmt::Debug for Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+    SIZE::Buffer: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Framebuffer")
+            .field("buffer", &self.buffer)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("orientation", &self.orientation)
+            .field("dirty", &self.dirty)
+            .finish()
+    }
+}
+
+impl<SIZE> Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    /// Create a framebuffer for a display of the given size and rotation, with all pixels off.
+    ///
+    /// The whole framebuffer starts out marked as changed, so the first flush sends all of it.
+    pub fn new(size: SIZE, rotation: DisplayRotation) -> Self {
+        Self::for_size(&size, rotation.into())
+    }
+
+    /// Lay the framebuffer out for a display with the given orientation instead of the rotation
+    /// given to [`new`](Self::new).
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub(crate) fn for_size(size: &SIZE, orientation: Orientation) -> Self {
+        let mut framebuffer = Self {
+            buffer: NewZeroed::new_zeroed(),
+            width: size.width(),
+            height: size.height(),
+            orientation,
+            dirty: [CLEAN; MAX_PAGES],
+        };
+        framebuffer.mark_all();
+
+        framebuffer
+    }
+
+    /// Get the dimensions of the framebuffer taking the rotation into account.
+    pub fn dimensions(&self) -> (u8, u8) {
+        if self.orientation.is_transposed() {
+            (self.height, self.width)
+        } else {
+            (self.width, self.height)
+        }
+    }
+
+    /// Get the rotation the framebuffer was created for.
+    pub fn rotation(&self) -> DisplayRotation {
+        self.orientation.rotation()
+    }
+
+    /// Get the orientation the framebuffer was created for.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Change the orientation, keeping what has been drawn.
+    ///
+    /// Pixels stay at the same X and Y coordinates, so the image turns with the display. Going
+    /// between 0°/180° and 90°/270° rotations swaps the width and height of non-square displays,
+    /// and pixels that don't fit the new dimensions are dropped. The whole framebuffer is marked
+    /// as changed.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, Framebuffer};
+    ///
+    /// let mut framebuffer = Framebuffer::new(DisplaySize128x64, DisplayRotation::Rotate0);
+    /// framebuffer.set_pixel(10, 20, true);
+    /// framebuffer.set_pixel(100, 5, true);
+    ///
+    /// framebuffer.set_orientation(Orientation::Rotate90);
+    ///
+    /// assert_eq!(framebuffer.dimensions(), (64, 128));
+    /// assert_eq!(framebuffer.get_pixel(10, 20), Some(true));
+    /// // Outside the 64 pixel wide rotated display
+    /// assert_eq!(framebuffer.get_pixel(100, 5), None);
+    /// ```
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        if orientation.is_transposed() != self.orientation.is_transposed() {
+            self.transpose();
+        }
+
+        self.orientation = orientation;
+        self.mark_all();
+    }
+
+    /// Swap buffer columns and rows, so pixels keep their coordinates when the layout switches
+    /// between the unrotated and the transposed one. Only the square both layouts share is kept.
+    fn transpose(&mut self) {
+        let side = usize::from(self.width.min(self.height));
+        let stride = usize::from(self.width);
+        let pages = usize::from(self.height).div_ceil(8);
+        let buffer = self.buffer.as_mut();
+
+        let locate = |column: usize, row: usize| (row / 8 * stride + column, row % 8);
+
+        for column in 0..side {
+            for row in 0..column {
+                let (a, a_bit) = locate(column, row);
+                let (b, b_bit) = locate(row, column);
+                let (a_on, b_on) = ((buffer[a] >> a_bit) & 1, (buffer[b] >> b_bit) & 1);
+
+                buffer[a] = buffer[a] & !(1 << a_bit) | (b_on << a_bit);
+                buffer[b] = buffer[b] & !(1 << b_bit) | (a_on << b_bit);
+            }
+        }
+
+        // Columns right of the square on wide displays
+        for page in buffer.chunks_mut(stride).take(pages) {
+            page[side..].fill(0);
+        }
+
+        // Rows below the square on tall displays
+        for row in side..usize::from(self.height) {
+            for column in 0..side {
+                let (idx, bit) = locate(column, row);
+                buffer[idx] &= !(1 << bit);
+            }
+        }
+    }
+
+    /// Turn all pixels on or off.
+    pub fn fill(&mut self, value: bool) {
+        self.buffer.as_mut().fill(if value { 0xff } else { 0 });
+        self.mark_all();
+    }
+
+    /// Mark the whole display as changed.
+    pub(crate) fn mark_all(&mut self) {
+        let pages = usize::from(self.height).div_ceil(8);
+        let last_column = self.width.saturating_sub(1);
+        self.dirty
+            .iter_mut()
+            .take(pages)
+            .for_each(|span| *span = (0, last_column));
+    }
+
+    /// Whether any part of the buffer is marked as changed.
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.dirty.iter().any(|&(min, max)| min <= max)
+    }
+
+    /// Mark columns `min..=max` of a buffer page as changed.
+    pub(crate) fn mark(&mut self, page: usize, min: u8, max: u8) {
+        if let Some(span) = self.dirty.get_mut(page) {
+            span.0 = span.0.min(min);
+            span.1 = span.1.max(max);
+        }
+    }
+
+    /// Find the buffer byte and bit of a pixel, or `None` if it is outside the display.
+    fn locate(&self, x: u32, y: u32) -> Option<(usize, u32)> {
+        // The buffer can be larger than the display, so check against the display size
+        let (width, height) = self.dimensions();
+        if x >= width.into() || y >= height.into() {
+            return None;
+        }
+
+        let stride = usize::from(self.width);
+
+        let (idx, bit) = if self.orientation.is_transposed() {
+            let idx = ((x as usize) / 8 * stride) + (y as usize);
+            let bit = x % 8;
+
+            (idx, bit)
+        } else {
+            let idx = ((y as usize) / 8 * stride) + (x as usize);
+            let bit = y % 8;
+
+            (idx, bit)
+        };
+
+        Some((idx, bit))
+    }
+
+    /// Turn a pixel on or off. If the X and Y coordinates are out of the bounds of the display,
+    /// this method call is a noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
+        let (idx, bit) = match self.locate(x, y) {
+            Some(location) => location,
+            None => return,
+        };
+        let stride = usize::from(self.width);
+
+        let value = value as u8;
+
+        if let Some(byte) = self.buffer.as_mut().get_mut(idx) {
+            // Set pixel value in byte
+            // Ref this comment https://stackoverflow.com/questions/47981/how-do-you-set-clear-and-toggle-a-single-bit#comment46654671_47990
+            *byte = *byte & !(1 << bit) | (value << bit);
+
+            let column = (idx % stride) as u8;
+            self.mark(idx / stride, column, column);
+        }
+    }
+
+    /// Whether a pixel is on, or `None` if the coordinates are outside the display.
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<bool> {
+        let (idx, bit) = self.locate(x, y)?;
+
+        self.buffer
+            .as_ref()
+            .get(idx)
+            .map(|byte| byte & (1 << bit) != 0)
+    }
+
+    /// The page-packed pixel buffer.
+    ///
+    /// Byte `page * width + column` holds 8 vertically stacked pixels, rows `page * 8` to
+    /// `page * 8 + 7` of that column, with the top row in the least significant bit. `width` is
+    /// the width of the unrotated display, [`DisplaySize::width`]. With
+    /// [`Rotate0`](DisplayRotation::Rotate0) and [`Rotate180`](DisplayRotation::Rotate180) the
+    /// column is the X coordinate and the row the Y coordinate of a pixel. With
+    /// [`Rotate90`](DisplayRotation::Rotate90) and [`Rotate270`](DisplayRotation::Rotate270) the
+    /// column is the Y coordinate and the row the X coordinate. The display hardware takes care of
+    /// flipping the image, so 180° and 270° rotations use the same layout as 0° and 90°, and
+    /// mirrored [orientations](Orientation) the same layout as their rotation.
+    ///
+    /// The buffer can be longer than the display needs, for example with
+    /// [`DisplaySizeDynamic`](crate::size::DisplaySizeDynamic). Bytes past the display are never
+    /// sent.
+    pub fn buffer(&self) -> &SIZE::Buffer {
+        &self.buffer
+    }
+
+    /// The page-packed pixel buffer for writing, with the layout described in
+    /// [`buffer`](Self::buffer).
+    ///
+    /// The whole display is marked as changed, because any of it may be written.
+    pub fn buffer_mut(&mut self) -> &mut SIZE::Buffer {
+        self.mark_all();
+        &mut self.buffer
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Buffer columns and rows covered by a rectangle given as `(x, y, width, height)` in display
+    /// coordinates, both end exclusive. The rectangle is clipped to the display, `None` if
+    /// nothing is left of it.
+    fn buffer_span(&self, area: (u32, u32, u32, u32)) -> Option<((u32, u32), (u32, u32))> {
+        let (width, height) = self.dimensions();
+        let x_end = area.0.saturating_add(area.2).min(width.into());
+        let y_end = area.1.saturating_add(area.3).min(height.into());
+        if area.0 >= x_end || area.1 >= y_end {
+            return None;
+        }
+
+        Some(if self.orientation.is_transposed() {
+            ((area.1, y_end), (area.0, x_end))
+        } else {
+            ((area.0, x_end), (area.1, y_end))
+        })
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Turn all pixels of a rectangle on or off a page byte at a time. The rectangle is given as
+    /// `(x, y, width, height)` in display coordinates and is clipped to the display.
+    pub(crate) fn fill_rect(&mut self, area: (u32, u32, u32, u32), value: bool) {
+        let ((col_start, col_end), (row_start, row_end)) = match self.buffer_span(area) {
+            Some(span) => span,
+            None => return,
+        };
+
+        let stride = usize::from(self.width);
+
+        for page in row_start / 8..=(row_end - 1) / 8 {
+            // Bits of this page inside the rectangle
+            let top = row_start.max(page * 8) - page * 8;
+            let bottom = (row_end - page * 8).min(8);
+            let mask = ((0xFFu16 << top) & ((1 << bottom) - 1)) as u8;
+
+            let start = page as usize * stride;
+            let columns = start + col_start as usize..start + col_end as usize;
+            if let Some(bytes) = self.buffer.as_mut().get_mut(columns) {
+                if value {
+                    bytes.iter_mut().for_each(|byte| *byte |= mask);
+                } else {
+                    bytes.iter_mut().for_each(|byte| *byte &= !mask);
+                }
+
+                self.mark(page as usize, col_start as u8, (col_end - 1) as u8);
+            }
+        }
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Set the pixels of a rectangle from values in row-major order. The rectangle is given as
+    /// `(x, y, width, height)` in display coordinates, values for pixels outside the display are
+    /// skipped.
+    pub(crate) fn fill_rows(
+        &mut self,
+        area: (i32, i32, u32, u32),
+        values: impl IntoIterator<Item = bool>,
+    ) {
+        let (width, height) = self.dimensions();
+        let stride = usize::from(self.width);
+        let transposed = self.orientation.is_transposed();
+
+        let x_start = i64::from(area.0);
+        let visible = x_start.max(0)..(x_start + i64::from(area.2)).min(width.into());
+        let mut values = values.into_iter();
+
+        for y in (i64::from(area.1)..).take(area.3 as usize) {
+            let row = values.by_ref().take(area.2 as usize);
+            if visible.is_empty() || !(0..i64::from(height)).contains(&y) {
+                row.for_each(drop);
+                continue;
+            }
+
+            let y = y as usize;
+            for (x, value) in (x_start..).zip(row) {
+                if !visible.contains(&x) {
+                    continue;
+                }
+
+                let x = x as usize;
+                let (idx, bit) = if transposed {
+                    (x / 8 * stride + y, x % 8)
+                } else {
+                    (y / 8 * stride + x, y % 8)
+                };
+                if let Some(byte) = self.buffer.as_mut().get_mut(idx) {
+                    *byte = *byte & !(1 << bit) | ((value as u8) << bit);
+                }
+            }
+
+            // Mark the whole visible row at once instead of every pixel
+            let (first, last) = (visible.start as usize, visible.end as usize - 1);
+            if transposed {
+                for page in first / 8..=last / 8 {
+                    self.mark(page, y as u8, y as u8);
+                }
+            } else {
+                self.mark(y / 8, first as u8, last as u8);
+            }
+        }
+    }
+
+    /// The first run of columns in a page that needs to be sent, as an inclusive `(min, max)`.
+    ///
+    /// Without a shadow copy this is the page's changed span. Otherwise it is the first run of
+    /// bytes that differ from the copy, ending where the unchanged gap gets longer than
+    /// addressing another area would cost. Pages without any differences are marked clean.
+    fn first_run(&mut self, page: usize, shadow: Option<&[u8]>) -> Option<(u8, u8)> {
+        let (min, max) = *self.dirty.get(page)?;
+        if min > max {
+            return None;
+        }
+        let shadow = match shadow {
+            Some(shadow) => shadow,
+            None => return Some((min, max)),
+        };
+
+        let start = page * usize::from(self.width);
+        let buffer = self.buffer.as_ref();
+        let changed = |column: u8| {
+            let idx = start + usize::from(column);
+            buffer.get(idx) != shadow.get(idx)
+        };
+
+        let first = match (min..=max).find(|&column| changed(column)) {
+            Some(first) => first,
+            None => {
+                self.dirty[page] = CLEAN;
+                return None;
+            }
+        };
+
+        let mut last = first;
+        for column in first..=max {
+            if changed(column) {
+                last = column;
+            } else if usize::from(column - last) > AREA_OVERHEAD {
+                break;
+            }
+        }
+
+        Some((first, last))
+    }
+
+    /// Take the next area that needs to be sent and mark it as sent, or `None` when the display
+    /// is up to date.
+    ///
+    /// Areas cover a single page, unless `merge` is set. Then neighbouring pages are combined
+    /// into one area when sending the columns in between is cheaper than addressing another
+    /// area.
+    ///
+    /// With a `shadow` copy of display RAM, only bytes that differ from it are sent. The caller
+    /// copies each page into it once the page has been sent.
+    pub(crate) fn next_dirty_area(
+        &mut self,
+        size: &SIZE,
+        controller: Controller,
+        merge: bool,
+        shadow: Option<&[u8]>,
+    ) -> Option<DirtyArea> {
+        let cost = |(first, last, min, max): (u8, u8, u8, u8)| {
+            usize::from(last - first + 1) * usize::from(max - min + 1)
+        };
+
+        // Pages and columns covered by the area, all inclusive
+        let mut current: Option<(u8, u8, u8, u8)> = None;
+
+        for page in 0..MAX_PAGES {
+            let (min, max) = match self.first_run(page, shadow) {
+                Some(run) => run,
+                None => continue,
+            };
+
+            let next = (page as u8, page as u8, min, max);
+            current = match current {
+                Some(run @ (first, _, run_min, run_max)) => {
+                    let merged = (first, page as u8, run_min.min(min), run_max.max(max));
+
+                    if merge && cost(merged) <= cost(run) + cost(next) + AREA_OVERHEAD {
+                        Some(merged)
+                    } else {
+                        break;
+                    }
+                }
+                None => Some(next),
+            };
+        }
+
+        let (first, last, min, max) = current?;
+
+        for page in usize::from(first)..=usize::from(last) {
+            // Columns after the area are still waiting to be sent
+            let span = &mut self.dirty[page];
+            *span = if span.0 <= span.1 && span.1 > max {
+                (max + 1, span.1)
+            } else {
+                CLEAN
+            };
+        }
+
+        Some(self.dirty_area(size, controller, (first, last, min, max)))
+    }
+
+    #[cfg(feature = "graphics")]
+    /// The area covering a rectangle given as `(x, y, width, height)` in display coordinates,
+    /// whether it has changed or not. `None` if the rectangle is outside the display.
+    ///
+    /// Change tracking is left alone.
+    pub(crate) fn area_of(
+        &self,
+        size: &SIZE,
+        controller: Controller,
+        rect: (u32, u32, u32, u32),
+    ) -> Option<DirtyArea> {
+        let ((col_start, col_end), (row_start, row_end)) = self.buffer_span(rect)?;
+        // The span is clipped to the display, so it fits in a `u8`
+        let pages = (
+            (row_start / 8) as u8,
+            ((row_end - 1) / 8) as u8,
+            col_start as u8,
+            (col_end - 1) as u8,
+        );
+
+        Some(self.dirty_area(size, controller, pages))
+    }
+
+    /// Build the area for the inclusive `(first page, last page, min column, max column)` span
+    /// of the buffer.
+    fn dirty_area(
+        &self,
+        size: &SIZE,
+        controller: Controller,
+        (first, last, min, max): (u8, u8, u8, u8),
+    ) -> DirtyArea {
+        let offset_x = size::column_offset(size, controller, self.orientation);
+        let offset_y = size.offset_y();
+        let upper_left = (min, first * 8);
+        // The column is exclusive and the row inclusive, like the other areas
+        let lower_right = (max.saturating_add(1), (last * 8 + 7).min(self.height - 1));
+
+        DirtyArea {
+            draw_start: (
+                upper_left.0.saturating_add(offset_x),
+                upper_left.1.saturating_add(offset_y),
+            ),
+            draw_end: (
+                lower_right.0.saturating_add(offset_x),
+                (lower_right.1 + 1).saturating_add(offset_y),
+            ),
+            disp_width: usize::from(self.width),
+            upper_left,
+            lower_right,
+        }
+    }
+}
+
+/// The part of the buffer that needs to be sent to the display on the next flush.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct DirtyArea {
+    /// Draw area start in display RAM, passed to `set_draw_area`. In page addressing mode this is
+    /// the column and row of the first page written.
+    pub draw_start: (u8, u8),
+    /// Draw area end in display RAM, passed to `set_draw_area`
+    pub draw_end: (u8, u8),
+    /// Width of a buffer page in bytes
+    pub disp_width: usize,
+    /// Upper left corner of the changed area in buffer coordinates
+    pub upper_left: (u8, u8),
+    /// Lower right corner of the changed area in buffer coordinates
+    pub lower_right: (u8, u8),
+}
+
+impl DirtyArea {
+    /// An area of display RAM from `start` to the exclusive `end` that isn't backed by a buffer,
+    /// for clearing it with [`zeros`](Self::zeros). `None` if it is empty.
+    pub fn ram(start: (u8, u8), end: (u8, u8)) -> Option<Self> {
+        let width = end.0.checked_sub(start.0).filter(|&width| width > 0)?;
+        let height = end.1.checked_sub(start.1).filter(|&height| height > 0)?;
+        // Keep the rows in the same place within their pages as in display RAM
+        let top = start.1 % 8;
+
+        Some(Self {
+            draw_start: start,
+            draw_end: end,
+            disp_width: usize::from(width),
+            upper_left: (0, top),
+            lower_right: (width, top.checked_add(height - 1)?),
+        })
+    }
+
+    /// The buffer pages the area covers.
+    pub fn pages(&self) -> RangeInclusive<u8> {
+        self.upper_left.1 / 8..=self.lower_right.1 / 8
+    }
+
+    /// The bytes of one buffer page of the area, or `None` if the area doesn't fit in `buffer`.
+    pub fn page_data<'a>(&self, buffer: &'a [u8], page: u8) -> Option<&'a [u8]> {
+        buffer.get(self.page_columns(page)?)
+    }
+
+    /// The buffer indices of one buffer page of the area.
+    pub fn page_columns(&self, page: u8) -> Option<Range<usize>> {
+        let start = usize::from(page).checked_mul(self.disp_width)?;
+
+        Some(
+            start.checked_add(usize::from(self.upper_left.0))?
+                ..start.checked_add(usize::from(self.lower_right.0))?,
+        )
+    }
+
+    /// Zeros for one page of the area.
+    pub fn zeros(&self) -> &'static [u8] {
+        const ZEROS: [u8; 256] = [0; 256];
+
+        &ZEROS[..usize::from(self.lower_right.0 - self.upper_left.0)]
+    }
+
+    /// The part of the area in one buffer page, for addressing modes that can't send several
+    /// pages of an area as one stream.
+    pub fn page(self, page: u8) -> Self {
+        let top = (page * 8).max(self.upper_left.1) - self.upper_left.1;
+        let bottom = self.lower_right.1 - (page * 8 + 7).min(self.lower_right.1);
+
+        Self {
+            draw_start: (self.draw_start.0, self.draw_start.1 + top),
+            draw_end: (self.draw_end.0, self.draw_end.1 - bottom),
+            upper_left: (self.upper_left.0, self.upper_left.1 + top),
+            lower_right: (self.lower_right.0, self.lower_right.1 - bottom),
+            ..self
+        }
+    }
+}
+
+#[cfg(feature = "graphics")]
+use core::convert::{Infallible, TryFrom};
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    image::GetPixel,
+    pixelcolor::BinaryColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<SIZE> Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    /// Clip a rectangle to the display and convert it to `(x, y, width, height)`, or `None` if
+    /// nothing is left of it.
+    pub(crate) fn clip(&self, area: &Rectangle) -> Option<(u32, u32, u32, u32)> {
+        let area = area.intersection(&self.bounding_box());
+        if area.is_zero_sized() {
+            return None;
+        }
+
+        Some((
+            area.top_left.x as u32,
+            area.top_left.y as u32,
+            area.size.width,
+            area.size.height,
+        ))
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<SIZE> DrawTarget for Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| {
+                self.set_pixel(pos.x as u32, pos.y as u32, color.is_on())
+            });
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        if let Some(area) = self.clip(area) {
+            self.fill_rect(area, color.is_on());
+        }
+
+        Ok(())
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let area = (
+            area.top_left.x,
+            area.top_left.y,
+            area.size.width,
+            area.size.height,
+        );
+        self.fill_rows(area, colors.into_iter().map(|color| color.is_on()));
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill(color.is_on());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<SIZE> OriginDimensions for Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<SIZE> GetPixel for Framebuffer<SIZE>
+where
+    SIZE: DisplaySize,
+{
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<BinaryColor> {
+        let x = u32::try_from(p.x).ok()?;
+        let y = u32::try_from(p.y).ok()?;
+
+        self.get_pixel(x, y).map(BinaryColor::from)
+    }
+}
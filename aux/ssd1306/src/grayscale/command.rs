@@ -0,0 +1,160 @@
+//! Grayscale display commands.
+
+use super::GrayController;
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+
+/// Commands understood by the grayscale controllers.
+///
+/// The opcodes differ between controllers, so commands are encoded for a specific
+/// [`GrayController`] when they are sent.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Set contrast. Higher number is higher contrast.
+    Contrast(u8),
+    /// Select how the contents of display RAM are shown.
+    DisplayMode(DisplayMode),
+    /// Turn display on or off.
+    DisplayOn(bool),
+    /// Set column start and end address, in units of
+    /// [`GrayController::column_width`] pixels.
+    ColumnAddress(u8, u8),
+    /// Set row start and end address from 0-127.
+    RowAddress(u8, u8),
+    /// Enable writing the data that follows to display RAM. Only needed by the SSD1322.
+    WriteRam,
+    /// Set the re-map register. The bits are controller specific, see
+    /// [`GrayController::remap`].
+    Remap(u8),
+    /// Set display start line from 0-127.
+    StartLine(u8),
+    /// Set vertical shift from 0-127.
+    DisplayOffset(u8),
+    /// Set multiplex ratio from 15-127 (MUX-1).
+    Multiplex(u8),
+    /// Enable the internal VDD regulator.
+    InternalVdd(bool),
+    /// Set up phase 1 and 2 of the pixel drive period. Each value must be in the range 1 - 15.
+    PhaseLength(u8, u8),
+    /// Set up display clock.
+    /// First value is oscillator frequency, increasing with higher value
+    /// Second value is divide ratio - 1
+    DisplayClockDiv(u8, u8),
+    /// Set the second precharge period from 1-15.
+    SecondPrecharge(u8),
+    /// Use the default linear gray scale table.
+    LinearGrayTable,
+    /// Set the precharge voltage level.
+    PrechargeVoltage(u8),
+    /// Set the VCOMH deselect level.
+    Vcomh(u8),
+    /// Set function selection B. Only available on the SSD1327.
+    FunctionSelectB(u8),
+    /// Set display enhancement A. Only available on the SSD1322.
+    DisplayEnhancement(u8, u8),
+    /// Lock or unlock the command interface.
+    CommandLock(bool),
+}
+
+/// How display RAM is shown on the panel.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DisplayMode {
+    /// Show display RAM as is.
+    Normal,
+    /// Turn all pixels fully on.
+    AllOn,
+    /// Turn all pixels off.
+    AllOff,
+    /// Show display RAM inverted.
+    Inverse,
+}
+
+impl Command {
+    /// Send command to the given controller.
+    ///
+    /// The SSD1322 expects command arguments as data, so they are sent separately from the
+    /// opcode.
+    pub fn send<DI>(self, controller: GrayController, iface: &mut DI) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+    {
+        let (data, len) = self.encode(controller)?;
+
+        match controller {
+            GrayController::Ssd1327 => iface.send_commands(U8(&data[..len])),
+            GrayController::Ssd1322 => {
+                iface.send_commands(U8(&data[..1]))?;
+
+                if len > 1 {
+                    iface.send_data(U8(&data[1..len]))?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Encode the command for the given controller, returning a buffer and the number of bytes
+    /// used.
+    ///
+    /// Commands the controller doesn't have are rejected with
+    /// [`DisplayError::InvalidFormatError`].
+    pub(crate) fn encode(
+        self,
+        controller: GrayController,
+    ) -> Result<([u8; 3], usize), DisplayError> {
+        use GrayController::{Ssd1322, Ssd1327};
+
+        let encoded = match (self, controller) {
+            (Command::Contrast(val), Ssd1327) => Self::pad(&[0x81, val]),
+            (Command::Contrast(val), Ssd1322) => Self::pad(&[0xC1, val]),
+            (Command::DisplayMode(mode), Ssd1327) => Self::pad(&[match mode {
+                DisplayMode::Normal => 0xA4,
+                DisplayMode::AllOn => 0xA5,
+                DisplayMode::AllOff => 0xA6,
+                DisplayMode::Inverse => 0xA7,
+            }]),
+            (Command::DisplayMode(mode), Ssd1322) => Self::pad(&[match mode {
+                DisplayMode::AllOff => 0xA4,
+                DisplayMode::AllOn => 0xA5,
+                DisplayMode::Normal => 0xA6,
+                DisplayMode::Inverse => 0xA7,
+            }]),
+            (Command::DisplayOn(on), _) => Self::pad(&[0xAE | (on as u8)]),
+            (Command::ColumnAddress(start, end), _) => Self::pad(&[0x15, start, end]),
+            (Command::RowAddress(start, end), _) => Self::pad(&[0x75, 0x7F & start, 0x7F & end]),
+            (Command::WriteRam, Ssd1322) => Self::pad(&[0x5C]),
+            (Command::Remap(remap), Ssd1327) => Self::pad(&[0xA0, remap]),
+            // Second byte enables dual COM line mode, which all SSD1322 panels use
+            (Command::Remap(remap), Ssd1322) => Self::pad(&[0xA0, remap, 0x11]),
+            (Command::StartLine(line), _) => Self::pad(&[0xA1, 0x7F & line]),
+            (Command::DisplayOffset(offset), _) => Self::pad(&[0xA2, 0x7F & offset]),
+            (Command::Multiplex(ratio), Ssd1327) => Self::pad(&[0xA8, 0x7F & ratio]),
+            (Command::Multiplex(ratio), Ssd1322) => Self::pad(&[0xCA, 0x7F & ratio]),
+            (Command::InternalVdd(on), _) => Self::pad(&[0xAB, on as u8]),
+            (Command::PhaseLength(phase1, phase2), _) => {
+                Self::pad(&[0xB1, ((0xF & phase2) << 4) | (0xF & phase1)])
+            }
+            (Command::DisplayClockDiv(fosc, div), _) => {
+                Self::pad(&[0xB3, ((0xF & fosc) << 4) | (0xF & div)])
+            }
+            (Command::SecondPrecharge(period), _) => Self::pad(&[0xB6, 0xF & period]),
+            (Command::LinearGrayTable, _) => Self::pad(&[0xB9]),
+            (Command::PrechargeVoltage(level), Ssd1327) => Self::pad(&[0xBC, 0xF & level]),
+            (Command::PrechargeVoltage(level), Ssd1322) => Self::pad(&[0xBB, 0x1F & level]),
+            (Command::Vcomh(level), _) => Self::pad(&[0xBE, 0xF & level]),
+            (Command::FunctionSelectB(val), Ssd1327) => Self::pad(&[0xD5, val]),
+            (Command::DisplayEnhancement(a, b), Ssd1322) => Self::pad(&[0xB4, a, b]),
+            (Command::CommandLock(lock), _) => Self::pad(&[0xFD, 0x12 | ((lock as u8) << 2)]),
+            _ => return Err(DisplayError::InvalidFormatError),
+        };
+
+        Ok(encoded)
+    }
+
+    fn pad(data: &[u8]) -> ([u8; 3], usize) {
+        let mut padded = [0; 3];
+        padded[..data.len()].copy_from_slice(data);
+
+        (padded, data.len())
+    }
+}
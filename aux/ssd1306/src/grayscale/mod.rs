@@ -0,0 +1,425 @@
+//! Grayscale display driver.
+//!
+//! [`Ssd1327`] drives 4 bit grayscale OLED controllers such as the SSD1327 (128x128) and SSD1322
+//! (256x64). It shares the interfaces, [`DisplayRotation`], [`Brightness`] and
+//! [`DisplayConfig`] with the monochrome [`Ssd1306`](crate::Ssd1306) driver, so the same code
+//! can set up both kinds of display. Select the SSD1322 with [`Ssd1327::with_controller`].
+//!
+//! Display sizes implement [`GrayDisplaySize`](size::GrayDisplaySize), which uses `u16`
+//! coordinates for panels wider than 255 pixels.
+//!
+//! ```rust
+//! # use ssd1306::test_helpers::I2cStub;
+//! # let i2c = I2cStub;
+//! use embedded_graphics::{
+//!     pixelcolor::Gray4,
+//!     prelude::*,
+//!     primitives::{PrimitiveStyle, Rectangle},
+//! };
+//! use ssd1306::{
+//!     grayscale::{size::DisplaySize128x128, Ssd1327},
+//!     prelude::*,
+//!     I2CDisplayInterface,
+//! };
+//!
+//! let interface = I2CDisplayInterface::new(i2c);
+//! let mut display = Ssd1327::new(interface, DisplaySize128x128, DisplayRotation::Rotate0)
+//!     .into_gray4_mode();
+//! display.init().unwrap();
+//!
+//! Rectangle::new(Point::new(8, 8), Size::new(16, 16))
+//!     .into_styled(PrimitiveStyle::with_fill(Gray4::new(0x8)))
+//!     .draw(&mut display)
+//!     .unwrap();
+//!
+//! display.flush().unwrap();
+//! ```
+
+pub mod command;
+mod mode;
+pub mod size;
+
+pub use mode::Gray4Mode;
+
+use crate::{
+    brightness::Brightness,
+    error::Error,
+    mode::{BasicMode, DisplayConfig},
+    rotation::{DisplayRotation, Orientation},
+};
+use command::{Command, DisplayMode};
+use core::convert::{Infallible, TryFrom};
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+use size::GrayDisplaySize;
+
+/// Grayscale display controller driving the panel.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum GrayController {
+    /// Solomon Systech SSD1327, 128 x 128 display RAM
+    #[default]
+    Ssd1327,
+    /// Solomon Systech SSD1322, 480 x 128 display RAM. Only has parallel and SPI interfaces.
+    Ssd1322,
+}
+
+impl GrayController {
+    /// Number of pixels addressed by one column address.
+    pub const fn column_width(self) -> u16 {
+        match self {
+            GrayController::Ssd1327 => 2,
+            GrayController::Ssd1322 => 4,
+        }
+    }
+
+    /// Number of pixel columns in display RAM.
+    pub const fn ram_columns(self) -> u16 {
+        match self {
+            GrayController::Ssd1327 => 128,
+            GrayController::Ssd1322 => 480,
+        }
+    }
+
+    /// Re-map register value for the unrotated or the flipped (180 degree) orientation.
+    pub const fn remap(self, flipped: bool) -> u8 {
+        match (self, flipped) {
+            // Column, COM and odd/even COM split
+            (GrayController::Ssd1327, false) => 0x51,
+            // Nibble and odd/even COM split
+            (GrayController::Ssd1327, true) => 0x42,
+            // Nibble and COM
+            (GrayController::Ssd1322, false) => 0x14,
+            // Column
+            (GrayController::Ssd1322, true) => 0x02,
+        }
+    }
+
+    /// Whether the controller understands the given command.
+    pub fn supports(self, command: Command) -> bool {
+        command.encode(self).is_ok()
+    }
+
+    /// Clock, drive and precharge settings sent during initialisation.
+    pub(crate) const fn tuning(self) -> [Command; 6] {
+        match self {
+            GrayController::Ssd1327 => [
+                Command::DisplayClockDiv(0x0, 0x0),
+                Command::PhaseLength(0x1, 0xF),
+                Command::SecondPrecharge(0xF),
+                Command::PrechargeVoltage(0x08),
+                Command::Vcomh(0x07),
+                Command::FunctionSelectB(0x62),
+            ],
+            GrayController::Ssd1322 => [
+                Command::DisplayClockDiv(0x9, 0x1),
+                Command::PhaseLength(0x2, 0xE),
+                Command::SecondPrecharge(0x8),
+                Command::PrechargeVoltage(0x1F),
+                Command::Vcomh(0x07),
+                Command::DisplayEnhancement(0xA0, 0xFD),
+            ],
+        }
+    }
+}
+
+/// Grayscale OLED driver.
+///
+/// Note that some methods are only available when the display is configured in a certain mode.
+#[derive(Copy, Clone, Debug)]
+pub struct Ssd1327<DI, SIZE, MODE> {
+    interface: DI,
+    mode: MODE,
+    size: SIZE,
+    rotation: DisplayRotation,
+    controller: GrayController,
+}
+
+impl<DI, SIZE> Ssd1327<DI, SIZE, BasicMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    /// Create a basic grayscale display interface.
+    ///
+    /// Use the `into_*_mode` methods to enable more functionality.
+    pub fn new(interface: DI, size: SIZE, rotation: DisplayRotation) -> Self {
+        Self {
+            interface,
+            size,
+            mode: BasicMode,
+            rotation,
+            controller: GrayController::default(),
+        }
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1327<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    /// Convert the display into another interface mode.
+    fn into_mode<MODE2>(self, mode: MODE2) -> Ssd1327<DI, SIZE, MODE2> {
+        Ssd1327 {
+            mode,
+            interface: self.interface,
+            size: self.size,
+            rotation: self.rotation,
+            controller: self.controller,
+        }
+    }
+
+    /// Select the controller driving the panel. The default is [`GrayController::Ssd1327`].
+    ///
+    /// This must be called before the display is initialised.
+    pub fn with_controller(mut self, controller: GrayController) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    /// Get the controller driving the panel.
+    pub fn controller(&self) -> GrayController {
+        self.controller
+    }
+
+    /// Convert the display into a 4 bit grayscale buffered graphics mode, supporting
+    /// [embedded-graphics](https://crates.io/crates/embedded-graphics).
+    ///
+    /// See [Gray4Mode] for more information.
+    pub fn into_gray4_mode(self) -> Ssd1327<DI, SIZE, Gray4Mode<SIZE>> {
+        self.into_mode(Gray4Mode::new())
+    }
+
+    /// Initialise the display.
+    pub fn init_display(&mut self) -> Result<(), DisplayError> {
+        self.command(Command::CommandLock(false))?;
+        self.command(Command::DisplayOn(false))?;
+        let multiplex = SIZE::HEIGHT
+            .checked_sub(1)
+            .and_then(|multiplex| u8::try_from(multiplex).ok())
+            .ok_or(DisplayError::OutOfBoundsError)?;
+        self.command(Command::Multiplex(multiplex))?;
+        self.command(Command::DisplayOffset(0))?;
+        self.command(Command::StartLine(0))?;
+        self.set_rotation(self.rotation)?;
+        self.command(Command::InternalVdd(true))?;
+
+        for command in self.controller.tuning() {
+            self.command(command)?;
+        }
+
+        self.command(Command::LinearGrayTable)?;
+        self.set_brightness(Brightness::default())?;
+        self.command(Command::DisplayMode(DisplayMode::Normal))?;
+        self.command(Command::DisplayOn(true))
+    }
+
+    /// Send a raw buffer to the display.
+    pub fn draw(&mut self, buffer: &[u8]) -> Result<(), DisplayError> {
+        self.interface.send_data(U8(buffer))
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use ssd1306::{
+    ///     grayscale::{size::DisplaySize256x64, GrayController, Ssd1327},
+    ///     prelude::*,
+    /// };
+    ///
+    /// let display = Ssd1327::new(interface, DisplaySize256x64, DisplayRotation::Rotate90)
+    ///     .with_controller(GrayController::Ssd1322);
+    /// assert_eq!(display.dimensions(), (64, 256));
+    /// ```
+    pub fn dimensions(&self) -> (u16, u16) {
+        size::dimensions::<SIZE>(self.rotation)
+    }
+
+    /// Get the display rotation.
+    pub fn rotation(&self) -> DisplayRotation {
+        self.rotation
+    }
+
+    /// Set the display rotation.
+    ///
+    /// 90 and 270 degree rotations are done when drawing into the buffer, so the display is only
+    /// ever flipped.
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Error> {
+        self.rotation = rotation;
+
+        Ok(self.command(Command::Remap(self.controller.remap(is_flipped(rotation))))?)
+    }
+
+    /// Set the display orientation.
+    ///
+    /// Mirrored orientations are not supported and return [`Error::Interface`] with
+    /// [`DisplayError::InvalidFormatError`].
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Error> {
+        if orientation.is_mirrored() {
+            return Err(Error::Interface(DisplayError::InvalidFormatError));
+        }
+
+        self.set_rotation(orientation.rotation())
+    }
+
+    /// Change the display brightness.
+    ///
+    /// Only the contrast is used. The precharge settings are tuned along with the gray scale
+    /// table during initialisation.
+    pub fn set_brightness(&mut self, brightness: Brightness) -> Result<(), Error> {
+        Ok(self.command(Command::Contrast(brightness.contrast))?)
+    }
+
+    /// Turn the display on or off. The display can be drawn to and retains all
+    /// of its memory even while off.
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error> {
+        Ok(self.command(Command::DisplayOn(on))?)
+    }
+
+    /// Set the screen pixel inversion
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), Error> {
+        Ok(self.command(Command::DisplayMode(if invert {
+            DisplayMode::Inverse
+        } else {
+            DisplayMode::Normal
+        }))?)
+    }
+
+    /// Set the area of the panel any sent data is drawn to.
+    ///
+    /// `start` is inclusive and `end` exclusive, both in unrotated panel pixels. Columns must be
+    /// multiples of the controller's [`column_width`](GrayController::column_width).
+    ///
+    /// Returns [`Error::InvalidArea`] if the area is empty, its columns are not aligned, or it
+    /// doesn't fit on the panel or in display RAM.
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// use ssd1306::{
+    ///     grayscale::{size::DisplaySize256x64, GrayController, Ssd1327},
+    ///     prelude::*,
+    ///     Error,
+    /// };
+    ///
+    /// let mut display = Ssd1327::new(StubInterface, DisplaySize256x64, DisplayRotation::Rotate0)
+    ///     .with_controller(GrayController::Ssd1322);
+    ///
+    /// assert!(display.set_draw_area((0, 0), (256, 64)).is_ok());
+    /// assert!(matches!(display.set_draw_area((4, 8), (4, 16)), Err(Error::InvalidArea)));
+    /// // The SSD1322 addresses columns in groups of 4 pixels
+    /// assert!(matches!(display.set_draw_area((2, 0), (8, 8)), Err(Error::InvalidArea)));
+    /// assert!(matches!(display.set_draw_area((0, 0), (260, 64)), Err(Error::InvalidArea)));
+    /// ```
+    pub fn set_draw_area(&mut self, start: (u16, u16), end: (u16, u16)) -> Result<(), Error> {
+        let width = self.controller.column_width();
+        if start.0 >= end.0
+            || start.1 >= end.1
+            || end.0 > SIZE::WIDTH
+            || end.1 > SIZE::HEIGHT
+            || start.0 % width != 0
+            || end.0 % width != 0
+        {
+            return Err(Error::InvalidArea);
+        }
+
+        // Unused RAM columns to the right of the panel
+        let spare_columns = self
+            .controller
+            .ram_columns()
+            .checked_sub(SIZE::WIDTH)
+            .and_then(|spare| spare.checked_sub(SIZE::OFFSETX))
+            .ok_or(Error::InvalidArea)?;
+        let offset_x = if is_flipped(self.rotation) {
+            spare_columns
+        } else {
+            SIZE::OFFSETX
+        };
+        let offset_y = u32::from(SIZE::OFFSETY);
+        let address = |value: u32| u8::try_from(value).map_err(|_| Error::InvalidArea);
+
+        self.command(Command::ColumnAddress(
+            address(u32::from((offset_x + start.0) / width))?,
+            address(u32::from((offset_x + end.0) / width - 1))?,
+        ))?;
+        self.command(Command::RowAddress(
+            address(offset_y + u32::from(start.1))?,
+            address(offset_y + u32::from(end.1) - 1)?,
+        ))?;
+
+        if self.controller.supports(Command::WriteRam) {
+            self.command(Command::WriteRam)?;
+        }
+
+        Ok(())
+    }
+
+    /// Send a command, rejecting it if the controller doesn't support it.
+    fn command(&mut self, command: Command) -> Result<(), DisplayError> {
+        command.send(self.controller, &mut self.interface)
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1327<DI, SIZE, MODE> {
+    /// Reset the display.
+    pub fn reset<RST, DELAY>(
+        &mut self,
+        rst: &mut RST,
+        delay: &mut DELAY,
+    ) -> Result<(), Error<Infallible, RST::Error>>
+    where
+        RST: OutputPin,
+        DELAY: DelayNs,
+    {
+        fn inner_reset<RST, DELAY>(rst: &mut RST, delay: &mut DELAY) -> Result<(), RST::Error>
+        where
+            RST: OutputPin,
+            DELAY: DelayNs,
+        {
+            rst.set_high()?;
+            delay.delay_ms(1);
+            rst.set_low()?;
+            delay.delay_ms(10);
+            rst.set_high()
+        }
+
+        inner_reset(rst, delay).map_err(Error::Pin)
+    }
+
+    /// Consume the display and return the underlying interface.
+    pub fn release(self) -> DI {
+        self.interface
+    }
+}
+
+impl<DI, SIZE> DisplayConfig for Ssd1327<DI, SIZE, BasicMode>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    type Error = DisplayError;
+
+    /// Set the display rotation.
+    fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+        Ok(self.set_rotation(rot)?)
+    }
+
+    /// Set the display orientation.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation)?)
+    }
+
+    /// Initialise the display.
+    fn init(&mut self) -> Result<(), DisplayError> {
+        self.init_display()
+    }
+}
+
+/// Whether the display is flipped by 180 degrees for the given rotation.
+const fn is_flipped(rotation: DisplayRotation) -> bool {
+    matches!(
+        rotation,
+        DisplayRotation::Rotate180 | DisplayRotation::Rotate270
+    )
+}
@@ -0,0 +1,243 @@
+//! 4 bit grayscale buffered graphics mode.
+
+use super::{size::GrayDisplaySize, Ssd1327};
+use crate::{
+    mode::DisplayConfig,
+    rotation::{DisplayRotation, Orientation},
+    size::NewZeroed,
+};
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+
+/// 4 bit grayscale buffered graphics mode.
+///
+/// This mode keeps a pixel buffer in system memory, 2 pixels per byte, up to 8192 bytes for
+/// 128x128px displays. This buffer is drawn to by [`set_pixel`](Ssd1327::set_pixel) commands or
+/// [`embedded-graphics`](https://docs.rs/embedded-graphics) commands. The display can then be
+/// updated using the [`flush`](Ssd1327::flush) method.
+#[derive(Clone, Debug)]
+pub struct Gray4Mode<SIZE>
+where
+    SIZE: GrayDisplaySize,
+{
+    buffer: SIZE::Buffer,
+    min_x: u16,
+    max_x: u16,
+    min_y: u16,
+    max_y: u16,
+}
+
+impl<SIZE> Gray4Mode<SIZE>
+where
+    SIZE: GrayDisplaySize,
+{
+    /// Create a new grayscale buffered graphics mode instance.
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: NewZeroed::new_zeroed(),
+            min_x: u16::MAX,
+            max_x: 0,
+            min_y: u16::MAX,
+            max_y: 0,
+        }
+    }
+
+    /// Fill the buffer and mark the whole display as changed.
+    fn clear(&mut self, luma: u8) {
+        let luma = luma & 0xF;
+        self.buffer.as_mut().fill(luma << 4 | luma);
+
+        self.min_x = 0;
+        self.max_x = SIZE::WIDTH.saturating_sub(1);
+        self.min_y = 0;
+        self.max_y = SIZE::HEIGHT.saturating_sub(1);
+    }
+
+    /// Set a pixel to a 4 bit luma value, keeping track of the changed area. Out of bounds pixels
+    /// are ignored.
+    ///
+    /// The buffer is laid out like the unrotated panel, so 90 and 270 degree rotations are
+    /// transposed here.
+    fn set_pixel(&mut self, rotation: DisplayRotation, x: u32, y: u32, luma: u8) {
+        let (x, y) = match rotation {
+            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (x, y),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
+                match y
+                    .checked_add(1)
+                    .and_then(|y| u32::from(SIZE::WIDTH).checked_sub(y))
+                {
+                    Some(x_phys) => (x_phys, x),
+                    None => return,
+                }
+            }
+        };
+
+        if x >= u32::from(SIZE::WIDTH) || y >= u32::from(SIZE::HEIGHT) {
+            return;
+        }
+
+        let (x, y) = (x as u16, y as u16);
+        let idx = y as usize * SIZE::WIDTH as usize / 2 + x as usize / 2;
+        // The left pixel of each pair is in the high nibble
+        let shift = if x % 2 == 0 { 4 } else { 0 };
+
+        // Sizes implemented outside this crate may have a buffer too small for their dimensions
+        let byte = match self.buffer.as_mut().get_mut(idx) {
+            Some(byte) => byte,
+            None => return,
+        };
+        *byte = *byte & !(0xF << shift) | ((luma & 0xF) << shift);
+
+        self.min_x = self.min_x.min(x);
+        self.max_x = self.max_x.max(x);
+        self.min_y = self.min_y.min(y);
+        self.max_y = self.max_y.max(y);
+    }
+
+    /// Get the area that has changed since the last call as start and exclusive end corners, and
+    /// reset the change tracking. Columns are widened to multiples of `column_width`.
+    ///
+    /// Returns `None` if nothing has changed.
+    fn take_dirty_area(&mut self, column_width: u16) -> Option<((u16, u16), (u16, u16))> {
+        if self.max_x < self.min_x || self.max_y < self.min_y {
+            return None;
+        }
+
+        let start = (self.min_x / column_width * column_width, self.min_y);
+        let end = (
+            (self.max_x / column_width + 1) * column_width,
+            self.max_y + 1,
+        );
+
+        self.min_x = u16::MAX;
+        self.max_x = 0;
+        self.min_y = u16::MAX;
+        self.max_y = 0;
+
+        Some((start, end))
+    }
+}
+
+impl<DI, SIZE> DisplayConfig for Ssd1327<DI, SIZE, Gray4Mode<SIZE>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    type Error = DisplayError;
+
+    /// Set the display rotation
+    ///
+    /// This method does not clear the screen.
+    fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
+        Ok(self.set_rotation(rot)?)
+    }
+
+    /// Set the display orientation
+    ///
+    /// This method does not clear the screen.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation)?)
+    }
+
+    /// Initialise and clear the display in graphics mode.
+    fn init(&mut self) -> Result<(), DisplayError> {
+        self.mode.clear(0);
+        self.init_display()
+    }
+}
+
+impl<DI, SIZE> Ssd1327<DI, SIZE, Gray4Mode<SIZE>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    /// Clear the underlying framebuffer. You need to call `disp.flush()` for any effect on the screen.
+    pub fn clear_buffer(&mut self) {
+        self.mode.clear(0);
+    }
+
+    /// Write out data to a display.
+    ///
+    /// This only updates the parts of the display that have changed since the last flush.
+    pub fn flush(&mut self) -> Result<(), DisplayError> {
+        let (start, end) = match self.mode.take_dirty_area(self.controller.column_width()) {
+            Some(area) => area,
+            None => return Ok(()),
+        };
+
+        self.set_draw_area(start, end)?;
+
+        let row_bytes = SIZE::WIDTH as usize / 2;
+        let columns = start.0 as usize / 2..end.0 as usize / 2;
+        let interface = &mut self.interface;
+
+        self.mode
+            .buffer
+            .as_mut()
+            .chunks(row_bytes)
+            .skip(start.1 as usize)
+            .take((end.1 - start.1) as usize)
+            .try_for_each(|row| {
+                let row = row
+                    .get(columns.clone())
+                    .ok_or(DisplayError::OutOfBoundsError)?;
+                interface.send_data(U8(row))
+            })
+    }
+
+    /// Set a pixel to a 4 bit gray level, where `0` is off and `15` fully on. If the X and Y
+    /// coordinates are out of the bounds of the display, this method call is a noop.
+    pub fn set_pixel(&mut self, x: u32, y: u32, luma: u8) {
+        self.mode.set_pixel(self.rotation, x, y, luma);
+    }
+}
+
+#[cfg(feature = "graphics")]
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::Size,
+    geometry::{Dimensions, OriginDimensions},
+    pixelcolor::{Gray4, GrayColor},
+    Pixel,
+};
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE> DrawTarget for Ssd1327<DI, SIZE, Gray4Mode<SIZE>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    type Color = Gray4;
+    type Error = DisplayError;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bb = self.bounding_box();
+
+        pixels
+            .into_iter()
+            .filter(|Pixel(pos, _color)| bb.contains(*pos))
+            .for_each(|Pixel(pos, color)| self.set_pixel(pos.x as u32, pos.y as u32, color.luma()));
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.mode.clear(color.luma());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE> OriginDimensions for Ssd1327<DI, SIZE, Gray4Mode<SIZE>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: GrayDisplaySize,
+{
+    fn size(&self) -> Size {
+        let (w, h) = self.dimensions();
+
+        Size::new(w.into(), h.into())
+    }
+}
@@ -0,0 +1,52 @@
+//! Grayscale display size.
+
+use crate::{rotation::DisplayRotation, size::NewZeroed};
+
+/// Grayscale display information.
+///
+/// The grayscale counterpart of [`DisplaySize`](crate::size::DisplaySize). Dimensions are `u16`
+/// since grayscale panels can be 256 pixels wide.
+pub trait GrayDisplaySize {
+    /// Width in pixels
+    const WIDTH: u16;
+
+    /// Height in pixels
+    const HEIGHT: u16;
+
+    /// Horizontal offset in pixels. Must be a multiple of the controller's
+    /// [`column_width`](super::GrayController::column_width).
+    const OFFSETX: u16 = 0;
+
+    /// Vertical offset in pixels
+    const OFFSETY: u16 = 0;
+
+    /// Size of framebuffer. With 4 bits per pixel, this is width * height / 2
+    type Buffer: AsMut<[u8]> + NewZeroed;
+}
+
+/// Get the display dimensions for the given rotation.
+pub(crate) fn dimensions<SIZE: GrayDisplaySize>(rotation: DisplayRotation) -> (u16, u16) {
+    match rotation {
+        DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (SIZE::WIDTH, SIZE::HEIGHT),
+        DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (SIZE::HEIGHT, SIZE::WIDTH),
+    }
+}
+
+/// Size information for 128x128 SSD1327 panels
+#[derive(Debug, Copy, Clone)]
+pub struct DisplaySize128x128;
+impl GrayDisplaySize for DisplaySize128x128 {
+    const WIDTH: u16 = 128;
+    const HEIGHT: u16 = 128;
+    type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 2];
+}
+
+/// Size information for 256x64 SSD1322 panels
+#[derive(Debug, Copy, Clone)]
+pub struct DisplaySize256x64;
+impl GrayDisplaySize for DisplaySize256x64 {
+    const WIDTH: u16 = 256;
+    const HEIGHT: u16 = 64;
+    const OFFSETX: u16 = 112;
+    type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 2];
+}
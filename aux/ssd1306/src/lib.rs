@@ -12,6 +12,15 @@
 //! - [`TerminalMode`] - A bufferless mode supporting drawing text to the display, as well as
 //!   setting cursor positions like a simple terminal.
 //!
+//! Besides the SSD1306, panels driven by the SH1106, SSD1305, SSD1309 and SSD1312 are supported by
+//! selecting their [`Controller`](controller::Controller) with [`Ssd1306::with_controller`].
+//!
+//! 4 bit grayscale panels driven by the SSD1327 or SSD1322 are supported by the
+//! [`Ssd1327`](grayscale::Ssd1327) driver in the [`grayscale`] module.
+//!
+//! With the `async` feature enabled, [`Ssd1306Async`](asynch::Ssd1306Async) provides the same
+//! modes over [`embedded-hal-async`](https://docs.rs/embedded-hal-async) I2C and SPI buses.
+//!
 //! # Examples
 //!
 //! Examples can be found in [the examples/
@@ -39,8 +48,9 @@
 //!     interface,
 //!     DisplaySize128x64,
 //!     DisplayRotation::Rotate0,
-//! ).into_buffered_graphics_mode();
-//! display.init().unwrap();
+//! ).into_buffered_graphics_mode()
+//! .init()
+//! .unwrap();
 //!
 //! let text_style = MonoTextStyleBuilder::new()
 //!     .font(&FONT_6X10)
@@ -75,8 +85,9 @@
 //!     interface,
 //!     DisplaySize128x64,
 //!     DisplayRotation::Rotate0,
-//! ).into_terminal_mode();
-//! display.init().unwrap();
+//! ).into_terminal_mode()
+//! .init()
+//! .unwrap();
 //! display.clear().unwrap();
 //!
 //! // Spam some characters to the display
@@ -108,149 +119,762 @@
 #![deny(unused_qualifications)]
 #![deny(rustdoc::broken_intra_doc_links)]
 
+#[cfg(feature = "async")]
+pub mod asynch;
 mod brightness;
 pub mod command;
+pub mod config;
+pub mod controller;
+#[cfg(feature = "embedded-hal-02")]
+pub mod eh02;
+pub mod emulator;
 mod error;
+mod framebuffer;
+pub mod grayscale;
 mod i2c_interface;
 pub mod mode;
+pub mod power;
 pub mod prelude;
 pub mod rotation;
 pub mod size;
 #[doc(hidden)]
 pub mod test_helpers;
 
-use core::convert::{Infallible, TryInto};
+use core::convert::{Infallible, TryFrom};
 
+#[cfg(feature = "async")]
+pub use crate::asynch::Ssd1306Async;
+pub use crate::config::Ssd1306Config;
+pub use crate::error::Error;
+pub use crate::framebuffer::Framebuffer;
 pub use crate::i2c_interface::I2CDisplayInterface;
 use crate::mode::BasicMode;
 use brightness::Brightness;
-use command::{AddrMode, Command, VcomhLevel};
-use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
-use embedded_hal::{blocking::delay::DelayMs, digital::v2::OutputPin};
-use error::Error;
-use mode::{BufferedGraphicsMode, TerminalMode};
-use rotation::DisplayRotation;
+use command::{AddrMode, Command, CommandBatch, Page};
+use config::INIT_STREAMS;
+use controller::Controller;
+use display_interface::{
+    DataFormat::{self, U8},
+    DisplayError, WriteOnlyDataCommand,
+};
+use embedded_hal::{delay::DelayNs, digital::OutputPin};
+use framebuffer::DirtyArea;
+use mode::{
+    BufferedGraphicsMode, DisplayConfig, InitializedMode, Shadowed, TerminalMode, Uninitialized,
+};
+use power::{PowerState, WAKE_DELAY_MS};
+use rotation::{DisplayRotation, Orientation};
 use size::DisplaySize;
 
 /// SSD1306 driver.
 ///
 /// Note that some methods are only available when the display is configured in a certain [`mode`].
+///
+/// The driver keeps the register values it last wrote, which can be read back with getters like
+/// [`is_inverted`](Ssd1306::is_inverted) and [`brightness`](Ssd1306::brightness). Setters don't
+/// send anything if the value doesn't change.
+///
+/// ```rust
+/// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+///
+/// let mut log = CommandLog::new();
+/// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+///     .init()
+///     .unwrap();
+///
+/// display.set_invert(true).unwrap();
+/// display.set_invert(true).unwrap();
+/// display.set_mirror(false).unwrap();
+/// display.set_brightness(Brightness::NORMAL).unwrap();
+/// assert!(display.is_inverted());
+/// assert!(!display.is_mirrored());
+/// # drop(display);
+///
+/// // Only the first inversion is sent after the display is turned on by `init`
+/// assert!(log.commands().ends_with(&[0xAF, 0xA7]));
+/// ```
 #[derive(Copy, Clone, Debug)]
 pub struct Ssd1306<DI, SIZE, MODE> {
     interface: DI,
     mode: MODE,
     size: SIZE,
     addr_mode: AddrMode,
-    rotation: DisplayRotation,
+    orientation: Orientation,
+    controller: Controller,
+    config: Ssd1306Config,
+    power: PowerState,
+    brightness: Brightness,
+    inverted: bool,
 }
 
-impl<DI, SIZE> Ssd1306<DI, SIZE, BasicMode>
+impl<DI, SIZE> Ssd1306<DI, SIZE, Uninitialized<BasicMode>>
 where
-    DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
 {
     /// Create a basic SSD1306 interface.
     ///
-    /// Use the `into_*_mode` methods to enable more functionality.
+    /// Use the `into_*_mode` methods to enable more functionality, then [`init`](Ssd1306::init)
+    /// the display to use it.
     pub fn new(interface: DI, size: SIZE, rotation: DisplayRotation) -> Self {
         Self {
             interface,
             size,
             addr_mode: AddrMode::Page,
-            mode: BasicMode,
-            rotation,
+            mode: Uninitialized::new(BasicMode),
+            orientation: rotation.into(),
+            controller: Controller::default(),
+            config: Ssd1306Config::new(),
+            power: PowerState::Off,
+            brightness: Brightness::NORMAL,
+            inverted: false,
         }
     }
 }
 
 impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE>
 where
-    DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
 {
     /// Convert the display into another interface mode.
     fn into_mode<MODE2>(self, mode: MODE2) -> Ssd1306<DI, SIZE, MODE2> {
+        self.map_mode(|_| mode)
+    }
+
+    /// Convert the display into a mode built from the current one.
+    fn map_mode<MODE2>(self, f: impl FnOnce(MODE) -> MODE2) -> Ssd1306<DI, SIZE, MODE2> {
         Ssd1306 {
-            mode,
+            mode: f(self.mode),
             addr_mode: self.addr_mode,
             interface: self.interface,
             size: self.size,
-            rotation: self.rotation,
+            orientation: self.orientation,
+            controller: self.controller,
+            config: self.config,
+            power: self.power,
+            brightness: self.brightness,
+            inverted: self.inverted,
         }
     }
 
+    /// Get the controller driving the panel.
+    pub fn controller(&self) -> Controller {
+        self.controller
+    }
+
+    /// Get the initialisation configuration.
+    pub fn config(&self) -> &Ssd1306Config {
+        &self.config
+    }
+
+    /// Get display dimensions, taking into account the current rotation of the display
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use ssd1306::{mode::TerminalMode, prelude::*, Ssd1306};
+    ///
+    /// let mut display = Ssd1306::new(
+    ///     interface,
+    ///     DisplaySize128x64,
+    ///     DisplayRotation::Rotate0,
+    /// ).into_terminal_mode();
+    /// assert_eq!(display.dimensions(), (128, 64));
+    ///
+    /// # let interface = StubInterface;
+    /// let mut rotated_display = Ssd1306::new(
+    ///     interface,
+    ///     DisplaySize128x64,
+    ///     DisplayRotation::Rotate90,
+    /// ).into_terminal_mode();
+    /// assert_eq!(rotated_display.dimensions(), (64, 128));
+    /// ```
+    pub fn dimensions(&self) -> (u8, u8) {
+        size::dimensions(&self.size, self.orientation)
+    }
+
+    /// Get the display rotation. Use [`orientation`](Ssd1306::orientation) to also see whether
+    /// the display is mirrored.
+    pub fn rotation(&self) -> DisplayRotation {
+        self.orientation.rotation()
+    }
+
+    /// Get the display orientation.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// Get the power state of the display.
+    pub fn power_state(&self) -> PowerState {
+        self.power
+    }
+
+    /// Get the addressing mode.
+    pub fn addr_mode(&self) -> AddrMode {
+        self.addr_mode
+    }
+
+    /// Get the display brightness.
+    pub fn brightness(&self) -> Brightness {
+        self.brightness
+    }
+
+    /// Whether the display is inverted.
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Whether the display is mirrored.
+    pub fn is_mirrored(&self) -> bool {
+        self.orientation.is_mirrored()
+    }
+
+    /// Send the initialisation sequence for the given addressing mode.
+    fn send_init(&mut self, mode: AddrMode) -> Result<(), DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+    {
+        self.init_commands(mode)?.send(&mut self.interface)?;
+        self.initialized(mode);
+
+        Ok(())
+    }
+
+    /// Create terminal mode with its cursor set up for the current dimensions.
+    fn terminal_mode(&self) -> TerminalMode {
+        let mut mode = TerminalMode::new();
+        mode.reset_cursor(self.dimensions());
+        mode
+    }
+
+    // The methods below choose the commands to send without sending them, so the blocking and
+    // async drivers only differ in how they wait for the interface. Setters that return `None`
+    // have nothing to do, otherwise the new value is stored once the commands have been sent.
+
+    /// The initialisation sequence for the given addressing mode.
+    pub(crate) fn init_commands(
+        &self,
+        mode: AddrMode,
+    ) -> Result<CommandBatch<INIT_STREAMS>, DisplayError> {
+        self.config
+            .init_batch(&self.size, self.controller, self.orientation, mode)
+    }
+
+    /// Store the register values set by the initialisation sequence.
+    pub(crate) fn initialized(&mut self, mode: AddrMode) {
+        self.addr_mode = mode;
+        self.power = PowerState::On;
+        self.brightness = self.config.brightness();
+        self.inverted = false;
+    }
+
+    /// Batch commands, rejecting them if the controller doesn't support one of them.
+    pub(crate) fn checked_commands<const N: usize>(
+        &self,
+        commands: &[Command],
+    ) -> Result<CommandBatch<N>, DisplayError> {
+        let mut batch = CommandBatch::new();
+        commands
+            .iter()
+            .try_for_each(|&command| batch.push_checked(command, self.controller))?;
+
+        Ok(batch)
+    }
+
+    /// Commands that change the addressing mode.
+    pub(crate) fn addr_mode_commands(
+        &self,
+        mode: AddrMode,
+    ) -> Result<Option<CommandBatch<1>>, DisplayError> {
+        if mode == self.addr_mode {
+            Ok(None)
+        } else if self.controller.supports_hv_addressing() {
+            self.checked_commands(&[Command::AddressMode(mode)])
+                .map(Some)
+        } else if mode != AddrMode::Page {
+            Err(DisplayError::InvalidFormatError)
+        } else {
+            Ok(Some(CommandBatch::new()))
+        }
+    }
+
+    /// Commands that change the display orientation.
+    pub(crate) fn orientation_commands(
+        &self,
+        orientation: Orientation,
+    ) -> Result<Option<CommandBatch<2>>, DisplayError> {
+        if orientation == self.orientation {
+            return Ok(None);
+        }
+
+        let (remap, reverse_com_dir) = orientation.remap_and_com_dir();
+        self.checked_commands(&[
+            Command::SegmentRemap(remap),
+            Command::ReverseComDir(reverse_com_dir),
+        ])
+        .map(Some)
+    }
+
+    /// Commands that change the display brightness.
+    pub(crate) fn brightness_commands(
+        &self,
+        brightness: Brightness,
+    ) -> Result<Option<CommandBatch<2>>, DisplayError> {
+        if brightness == self.brightness {
+            return Ok(None);
+        }
+
+        self.checked_commands(&[
+            Command::PreChargePeriod(
+                self.config.precharge_phase1(self.controller),
+                brightness.precharge,
+            ),
+            Command::Contrast(brightness.contrast),
+        ])
+        .map(Some)
+    }
+
+    /// Commands that turn the display on or off, restarting the charge pump of a sleeping
+    /// display. If `restore_contrast` is set, the contrast dimmed by [`sleep`](Ssd1306::sleep) is
+    /// restored as well.
+    pub(crate) fn display_on_commands(
+        &self,
+        on: bool,
+        restore_contrast: bool,
+    ) -> Result<Option<CommandBatch<3>>, DisplayError> {
+        match (on, self.power) {
+            (true, PowerState::On) | (false, PowerState::Off | PowerState::Asleep) => Ok(None),
+            (true, PowerState::Asleep) => {
+                let commands = [
+                    Command::ChargePump(true),
+                    Command::DisplayOn(true),
+                    Command::Contrast(self.brightness.contrast),
+                ];
+                let first = if self.uses_charge_pump() { 0 } else { 1 };
+                let last = if restore_contrast && self.config.dim_on_sleep() {
+                    3
+                } else {
+                    2
+                };
+
+                self.checked_commands(&commands[first..last]).map(Some)
+            }
+            _ => self.checked_commands(&[Command::DisplayOn(on)]).map(Some),
+        }
+    }
+
+    /// Commands that put the display to sleep.
+    pub(crate) fn sleep_commands(&self) -> Result<Option<CommandBatch<3>>, DisplayError> {
+        if self.power == PowerState::Asleep {
+            return Ok(None);
+        }
+
+        let commands: &[Command] = match (self.config.dim_on_sleep(), self.uses_charge_pump()) {
+            (true, true) => &[
+                Command::Contrast(0),
+                Command::DisplayOn(false),
+                Command::ChargePump(false),
+            ],
+            (true, false) => &[Command::Contrast(0), Command::DisplayOn(false)],
+            (false, true) => &[Command::DisplayOn(false), Command::ChargePump(false)],
+            (false, false) => &[Command::DisplayOn(false)],
+        };
+
+        self.checked_commands(commands).map(Some)
+    }
+
+    /// Commands that restore the contrast once a display woken up from sleep has settled.
+    pub(crate) fn wake_commands(&self) -> Result<CommandBatch<1>, DisplayError> {
+        if !self.config.dim_on_sleep() {
+            return Ok(CommandBatch::new());
+        }
+
+        self.checked_commands(&[Command::Contrast(self.brightness.contrast)])
+    }
+
+    /// Whether the display's charge pump is controlled by the driver.
+    fn uses_charge_pump(&self) -> bool {
+        self.config.charge_pump() && self.controller.supports(Command::ChargePump(true))
+    }
+
+    /// Commands that change the screen pixel on/off inversion.
+    pub(crate) fn invert_commands(
+        &self,
+        invert: bool,
+    ) -> Result<Option<CommandBatch<1>>, DisplayError> {
+        if invert == self.inverted {
+            return Ok(None);
+        }
+
+        self.checked_commands(&[Command::Invert(invert)]).map(Some)
+    }
+
+    /// Commands that set the draw area. See [`set_draw_area`](Ssd1306::set_draw_area).
+    pub(crate) fn draw_area_commands(
+        &self,
+        start: (u8, u8),
+        end: (u8, u8),
+    ) -> Result<CommandBatch<2>, Error> {
+        if start.0 >= end.0
+            || start.1 >= end.1
+            || end.0 > self.controller.ram_columns()
+            || end.1 > SIZE::DRIVER_ROWS
+        {
+            return Err(Error::InvalidArea);
+        }
+
+        let columns = Command::ColumnAddress(start.0, end.0 - 1);
+
+        let batch = if self.addr_mode == AddrMode::Page {
+            self.checked_commands(&[columns])?
+        } else {
+            self.checked_commands(&[
+                columns,
+                Command::PageAddressNum(start.1 / 8, (end.1 - 1) / 8),
+            ])?
+        };
+
+        Ok(batch)
+    }
+
+    /// Commands that move the RAM pointer to the page containing `row`. See
+    /// [`set_row`](Ssd1306::set_row).
+    pub(crate) fn row_commands(&self, row: u8) -> Result<CommandBatch<1>, Error> {
+        if row >= SIZE::DRIVER_ROWS {
+            return Err(Error::OutOfBounds);
+        }
+
+        Ok(self.checked_commands(&[Command::PageStart(Page::try_from(row)?)])?)
+    }
+
+    /// Commands that address one buffer page of an area, to be sent before the page.
+    ///
+    /// Horizontal addressing continues on the next page of the area by itself, so the area is
+    /// only addressed before its first page. Other addressing modes address each page.
+    pub(crate) fn area_page_commands(
+        &self,
+        area: &DirtyArea,
+        page: u8,
+    ) -> Result<CommandBatch<2>, DisplayError> {
+        let first_page = area.upper_left.1 / 8;
+
+        match self.addr_mode {
+            AddrMode::Page => {
+                let row = area.draw_start.1.saturating_add((page - first_page) * 8);
+                self.checked_commands(&[
+                    Command::PageStart(Page::try_from(row)?),
+                    Command::ColStart(area.draw_start.0),
+                ])
+            }
+            AddrMode::Horizontal if page != first_page => Ok(CommandBatch::new()),
+            AddrMode::Horizontal => Ok(self.draw_area_commands(area.draw_start, area.draw_end)?),
+            AddrMode::Vertical => {
+                let area = area.page(page);
+                Ok(self.draw_area_commands(area.draw_start, area.draw_end)?)
+            }
+        }
+    }
+
+    /// The commands and data that send one buffer page of an area.
+    pub(crate) fn area_page<'a>(
+        &self,
+        buffer: &'a [u8],
+        area: &DirtyArea,
+        page: u8,
+    ) -> Result<(CommandBatch<2>, &'a [u8]), DisplayError> {
+        Ok((
+            self.area_page_commands(area, page)?,
+            area.page_data(buffer, page)
+                .ok_or(DisplayError::OutOfBoundsError)?,
+        ))
+    }
+
+    /// The part of display RAM showing the panel, as the start and exclusive end of a draw area.
+    pub(crate) fn panel_area(&self) -> ((u8, u8), (u8, u8)) {
+        let offset_x = size::column_offset(&self.size, self.controller, self.orientation);
+        let offset_y = self.size.offset_y();
+
+        (
+            (offset_x, offset_y),
+            (
+                self.size.width().saturating_add(offset_x),
+                self.size.height().saturating_add(offset_y),
+            ),
+        )
+    }
+
+    /// Take the next changed area of a framebuffer to send with
+    /// [`flush_from`](Ssd1306::flush_from), or `None` once it is up to date or while the display
+    /// is asleep.
+    pub(crate) fn next_area_from(
+        &self,
+        framebuffer: &mut Framebuffer<SIZE>,
+    ) -> Result<Option<DirtyArea>, DisplayError> {
+        if framebuffer.orientation() != self.orientation
+            || framebuffer.dimensions() != self.dimensions()
+        {
+            return Err(DisplayError::OutOfBoundsError);
+        }
+        if self.power == PowerState::Asleep {
+            return Ok(None);
+        }
+
+        let merge = self.addr_mode == AddrMode::Horizontal;
+        Ok(framebuffer.next_dirty_area(&self.size, self.controller, merge, None))
+    }
+}
+
+impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, Uninitialized<MODE>>
+where
+    SIZE: DisplaySize,
+{
+    /// Select the controller driving the panel. The default is [`Controller::Ssd1306`].
+    pub fn with_controller(mut self, controller: Controller) -> Self {
+        self.controller = controller;
+        self
+    }
+
+    /// Set the register values used when the display is initialised. See [`Ssd1306Config`].
+    pub fn with_config(mut self, config: Ssd1306Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Set the orientation the display is initialised with, replacing the rotation given to
+    /// [`new`](Ssd1306::new). See [`Orientation`].
+    ///
+    /// ```rust
+    /// use ssd1306::{emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .with_orientation(Orientation::FlipHorizontal)
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// assert!(emulator.panel_pixel(127, 0));
+    /// ```
+    pub fn with_orientation(mut self, orientation: Orientation) -> Self
+    where
+        MODE: InitializedMode,
+    {
+        self.orientation = orientation;
+        self.mode.inner_mut().orientation_changed(orientation);
+        self
+    }
+
     /// Convert the display into a buffered graphics mode, supporting
     /// [embedded-graphics](https://crates.io/crates/embedded-graphics).
     ///
     /// See [BufferedGraphicsMode] for more information.
-    pub fn into_buffered_graphics_mode(self) -> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>> {
-        self.into_mode(BufferedGraphicsMode::new())
+    pub fn into_buffered_graphics_mode(
+        self,
+    ) -> Ssd1306<DI, SIZE, Uninitialized<BufferedGraphicsMode<SIZE>>> {
+        let mode = BufferedGraphicsMode::new(&self.size, self.orientation);
+        self.into_mode(Uninitialized::new(mode))
+    }
+
+    /// Convert the display into a buffered graphics mode that only flushes the bytes that
+    /// changed since they were last sent. It keeps a second copy of the buffer to do so.
+    ///
+    /// See [BufferedGraphicsMode] and [`Shadowed`] for more information.
+    pub fn into_shadowed_graphics_mode(
+        self,
+    ) -> Ssd1306<DI, SIZE, Uninitialized<BufferedGraphicsMode<SIZE, Shadowed>>> {
+        let mode = BufferedGraphicsMode::new(&self.size, self.orientation);
+        self.into_mode(Uninitialized::new(mode))
     }
 
     /// Convert the display into a text-only, terminal-like mode.
     ///
     /// See [TerminalMode] for more information.
-    pub fn into_terminal_mode(self) -> Ssd1306<DI, SIZE, TerminalMode> {
-        self.into_mode(TerminalMode::new())
+    pub fn into_terminal_mode(self) -> Ssd1306<DI, SIZE, Uninitialized<TerminalMode>> {
+        let mode = self.terminal_mode();
+        self.into_mode(Uninitialized::new(mode))
     }
 
-    /// Initialise the display in one of the available addressing modes.
-    pub fn init_with_addr_mode(&mut self, mode: AddrMode) -> Result<(), DisplayError> {
-        let rotation = self.rotation;
+    /// Initialise and configure the display for its mode, returning the initialised display.
+    ///
+    /// Methods that talk to the display are only available on the returned display, so they
+    /// can't be used before the display is initialised:
+    ///
+    /// ```rust,compile_fail
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use ssd1306::{prelude::*, Ssd1306};
+    ///
+    /// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    /// ```
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use ssd1306::{prelude::*, Ssd1306};
+    ///
+    /// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    /// ```
+    pub fn init<E>(self) -> Result<Ssd1306<DI, SIZE, MODE>, E>
+    where
+        Ssd1306<DI, SIZE, MODE>: DisplayConfig<Error = E>,
+    {
+        let mut display = self.map_mode(Uninitialized::into_inner);
+        DisplayConfig::init(&mut display)?;
 
-        Command::DisplayOn(false).send(&mut self.interface)?;           // good
-        Command::DisplayClockDiv(0x8, 0x0).send(&mut self.interface)?;  // good
-        Command::Multiplex(SIZE::HEIGHT - 1).send(&mut self.interface)?; // good
-        Command::DisplayOffset(0).send(&mut self.interface)?;               // good
-        Command::StartLine(0).send(&mut self.interface)?;                   // good
-        // TODO: Ability to turn charge pump on/off
-        Command::ChargePump(true).send(&mut self.interface)?;               // good 
-        Command::Invert(false).send(&mut self.interface)?;
-        Command::AllOn(false).send(&mut self.interface)?;
-        self.set_rotation(rotation)?;
-        self.size.configure(&mut self.interface)?;                      // modified
-        self.set_brightness(Brightness::default())?;
+        Ok(display)
+    }
+
+    /// Initialise the display in one of the available addressing modes, returning the
+    /// initialised display.
+    ///
+    /// The [`Ssd1306Config`] is validated first, so nothing is sent if it is invalid.
+    pub fn init_with_addr_mode(
+        self,
+        mode: AddrMode,
+    ) -> Result<Ssd1306<DI, SIZE, MODE>, DisplayError>
+    where
+        DI: WriteOnlyDataCommand,
+    {
+        let mut display = self.map_mode(Uninitialized::into_inner);
+        display.send_init(mode)?;
 
-        Command::VcomhDeselect(VcomhLevel::Auto).send(&mut self.interface)?;
+        Ok(display)
+    }
+}
 
+impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE>
+where
+    SIZE: DisplaySize,
+    MODE: InitializedMode,
+{
+    /// Convert the display into a buffered graphics mode, supporting
+    /// [embedded-graphics](https://crates.io/crates/embedded-graphics).
+    ///
+    /// See [BufferedGraphicsMode] for more information.
+    pub fn into_buffered_graphics_mode(self) -> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>> {
+        let mode = BufferedGraphicsMode::new(&self.size, self.orientation);
+        self.into_mode(mode)
+    }
 
-        Command::AddressMode(mode).send(&mut self.interface)?;              // good with Horizontol
+    /// Convert the display into a buffered graphics mode that only flushes the bytes that
+    /// changed since they were last sent. It keeps a second copy of the buffer to do so.
+    ///
+    /// See [BufferedGraphicsMode] and [`Shadowed`] for more information.
+    pub fn into_shadowed_graphics_mode(
+        self,
+    ) -> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, Shadowed>> {
+        let mode = BufferedGraphicsMode::new(&self.size, self.orientation);
+        self.into_mode(mode)
+    }
 
-        // Command::EnableScroll(false).send(&mut self.interface)?;
-        Command::DisplayOn(true).send(&mut self.interface)?;
+    /// Convert the display into a text-only, terminal-like mode.
+    ///
+    /// See [TerminalMode] for more information.
+    pub fn into_terminal_mode(self) -> Ssd1306<DI, SIZE, TerminalMode> {
+        let mode = self.terminal_mode();
+        self.into_mode(mode)
+    }
 
-        self.addr_mode = mode;
+    /// Store a new orientation once it has been sent, so the mode can follow it.
+    pub(crate) fn orientation_set(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+        self.mode.orientation_changed(orientation);
+    }
+}
 
-        Ok(())
+impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    MODE: InitializedMode,
+{
+    /// Initialise the display again in one of the available addressing modes, for example after
+    /// it has been [reset](Ssd1306::reset).
+    ///
+    /// The [`Ssd1306Config`] is validated first, so nothing is sent if it is invalid.
+    pub fn init_with_addr_mode(&mut self, mode: AddrMode) -> Result<(), DisplayError> {
+        self.send_init(mode)
     }
 
     /// Change the addressing mode
-    pub fn set_addr_mode(&mut self, mode: AddrMode) -> Result<(), DisplayError> {
-        Command::AddressMode(mode).send(&mut self.interface)?;
-        self.addr_mode = mode;
+    ///
+    /// Controllers without horizontal and vertical addressing are always in page addressing
+    /// mode, so only [`AddrMode::Page`] is accepted for them.
+    pub fn set_addr_mode(&mut self, mode: AddrMode) -> Result<(), Error> {
+        if let Some(commands) = self.addr_mode_commands(mode)? {
+            commands.send(&mut self.interface)?;
+            self.addr_mode = mode;
+        }
+
         Ok(())
     }
 
-    /// Send the data to the display for drawing at the current position in the framebuffer
-    /// and advance the position accordingly. Cf. `set_draw_area` to modify the affected area by
-    /// this method.
+    /// Send the part of `buffer` between `upper_left` and `lower_right` to the display.
+    ///
+    /// `buffer` is laid out like the framebuffer of
+    /// [`BufferedGraphicsMode`](crate::mode::BufferedGraphicsMode), i.e. `disp_width` bytes per
+    /// page, and the corners are in unrotated buffer pixels. The area is addressed the way the
+    /// current addressing mode needs, so this works with every controller and addressing mode.
     ///
     /// This method takes advantage of a bounding box for faster writes.
+    ///
+    /// Returns [`Error::InvalidArea`] if the area is empty or doesn't fit in `buffer`, and
+    /// [`Error::OutOfBounds`] if it doesn't fit in display RAM.
     pub fn bounded_draw(
         &mut self,
         buffer: &[u8],
         disp_width: usize,
         upper_left: (u8, u8),
         lower_right: (u8, u8),
-    ) -> Result<(), DisplayError> {
-        // Self::flush_buffer_chunks(
-        //     &mut self.interface,
-        self.flush_buffer_chunks_2(
-            buffer,
+    ) -> Result<(), Error> {
+        let pages_needed = usize::from(lower_right.1 / 8) + 1;
+        let fits_buffer = pages_needed
+            .checked_mul(disp_width)
+            .is_some_and(|len| len <= buffer.len());
+
+        if upper_left.0 >= lower_right.0
+            || upper_left.1 > lower_right.1
+            || usize::from(lower_right.0) > disp_width
+            || !fits_buffer
+        {
+            return Err(Error::InvalidArea);
+        }
+
+        let ((offset_x, offset_y), _) = self.panel_area();
+        let corner = |(x, y): (u8, u8)| {
+            offset_x
+                .checked_add(x)
+                .zip(offset_y.checked_add(y))
+                .ok_or(Error::OutOfBounds)
+        };
+        let area = DirtyArea {
+            draw_start: corner(upper_left)?,
+            draw_end: corner((lower_right.0, lower_right.1.saturating_add(1)))?,
             disp_width,
             upper_left,
             lower_right,
-        )
+        };
+
+        for page in area.pages() {
+            let (commands, data) = self.area_page(buffer, &area, page)?;
+            commands.send(&mut self.interface)?;
+            self.interface.send_data(U8(data))?;
+        }
+
+        Ok(())
     }
 
     /// Send a raw buffer to the display.
@@ -258,217 +882,358 @@ where
         self.interface.send_data(U8(&buffer))
     }
 
-    /// Get display dimensions, taking into account the current rotation of the display
+    /// Set the display rotation. Mirroring is kept as it is.
+    ///
+    /// Buffered graphics modes keep what has been drawn, so a screen can follow the way the
+    /// device is held without being redrawn.
     ///
     /// ```rust
-    /// # use ssd1306::test_helpers::StubInterface;
-    /// # let interface = StubInterface;
-    /// use ssd1306::{mode::TerminalMode, prelude::*, Ssd1306};
+    /// use ssd1306::{emulator::Emulator, prelude::*, Ssd1306};
     ///
-    /// let mut display = Ssd1306::new(
-    ///     interface,
-    ///     DisplaySize128x64,
-    ///     DisplayRotation::Rotate0,
-    /// ).into_terminal_mode();
-    /// assert_eq!(display.dimensions(), (128, 64));
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
     ///
-    /// # let interface = StubInterface;
-    /// let mut rotated_display = Ssd1306::new(
-    ///     interface,
-    ///     DisplaySize128x64,
-    ///     DisplayRotation::Rotate90,
-    /// ).into_terminal_mode();
-    /// assert_eq!(rotated_display.dimensions(), (64, 128));
+    /// display.set_pixel(10, 20, true);
+    /// display.flush().unwrap();
+    ///
+    /// display.set_rotation(DisplayRotation::Rotate90).unwrap();
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// // Rotated 90 degrees clockwise, (10, 20) is 20 pixels from the right edge of the panel
+    /// assert!(emulator.panel_pixel(107, 10));
+    /// assert!(!emulator.panel_pixel(10, 20));
     /// ```
-    pub fn dimensions(&self) -> (u8, u8) {
-        match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (SIZE::WIDTH, SIZE::HEIGHT),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (SIZE::HEIGHT, SIZE::WIDTH),
-        }
+    pub fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Error> {
+        self.set_orientation(Orientation::new(rotation, self.orientation.is_mirrored()))
     }
 
-    /// Get the display rotation.
-    pub fn rotation(&self) -> DisplayRotation {
-        self.rotation
+    /// Set mirror enabled/disabled. Mirroring flips the rotated image left to right, the
+    /// rotation is kept as it is.
+    pub fn set_mirror(&mut self, mirror: bool) -> Result<(), Error> {
+        self.set_orientation(Orientation::new(self.orientation.rotation(), mirror))
     }
 
-    /// Set the display rotation.
-    pub fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), DisplayError> {
-        self.rotation = rotation;
+    /// Set the display orientation, which combines the rotation and mirroring.
+    ///
+    /// This method does not clear the screen. Buffered graphics modes keep what has been drawn
+    /// at the same coordinates, so it turns with the display, and send all of it again on the
+    /// next flush. See [`Framebuffer::set_orientation`].
+    ///
+    /// ```rust
+    /// use ssd1306::{
+    ///     emulator::Emulator,
+    ///     prelude::*,
+    ///     size::{buffer_size, DisplaySizeCustom},
+    ///     Ssd1306,
+    /// };
+    ///
+    /// // A panel that isn't centred in display RAM
+    /// let size = DisplaySizeCustom::<64, 48, { buffer_size(64, 48) }, 10, 0>::new();
+    /// let mut emulator = Emulator::new(size);
+    /// let mut display = Ssd1306::new(&mut emulator, size, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// // Mounted upside down behind a mirror
+    /// display.set_orientation(Orientation::FlipVertical).unwrap();
+    /// display.set_pixel(1, 2, true);
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// assert!(emulator.panel_pixel(1, 45));
+    /// ```
+    pub fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Error> {
+        if let Some(commands) = self.orientation_commands(orientation)? {
+            commands.send(&mut self.interface)?;
+            self.orientation_set(orientation);
+        }
+
+        Ok(())
+    }
 
-        match rotation {
-            DisplayRotation::Rotate0 => {
-                Command::SegmentRemap(true).send(&mut self.interface)?;
-                Command::ReverseComDir(true).send(&mut self.interface)?;
-            }
-            DisplayRotation::Rotate90 => {
-                Command::SegmentRemap(false).send(&mut self.interface)?;
-                Command::ReverseComDir(true).send(&mut self.interface)?;
-            }
-            DisplayRotation::Rotate180 => {
-                Command::SegmentRemap(false).send(&mut self.interface)?;
-                Command::ReverseComDir(false).send(&mut self.interface)?;
-            }
-            DisplayRotation::Rotate270 => {
-                Command::SegmentRemap(true).send(&mut self.interface)?;
-                Command::ReverseComDir(false).send(&mut self.interface)?;
-            }
-        };
+    /// Change the display brightness.
+    pub fn set_brightness(&mut self, brightness: Brightness) -> Result<(), Error> {
+        if let Some(commands) = self.brightness_commands(brightness)? {
+            commands.send(&mut self.interface)?;
+            self.brightness = brightness;
+        }
 
         Ok(())
     }
 
-    /// Set mirror enabled/disabled.
-    pub fn set_mirror(&mut self, mirror: bool) -> Result<(), DisplayError> {
-        if mirror {
-            match self.rotation {
-                DisplayRotation::Rotate0 => {
-                    Command::SegmentRemap(false).send(&mut self.interface)?;
-                    Command::ReverseComDir(true).send(&mut self.interface)?;
-                }
-                DisplayRotation::Rotate90 => {
-                    Command::SegmentRemap(false).send(&mut self.interface)?;
-                    Command::ReverseComDir(false).send(&mut self.interface)?;
-                }
-                DisplayRotation::Rotate180 => {
-                    Command::SegmentRemap(true).send(&mut self.interface)?;
-                    Command::ReverseComDir(false).send(&mut self.interface)?;
-                }
-                DisplayRotation::Rotate270 => {
-                    Command::SegmentRemap(true).send(&mut self.interface)?;
-                    Command::ReverseComDir(true).send(&mut self.interface)?;
-                }
-            };
-        } else {
-            self.set_rotation(self.rotation)?;
+    /// Turn the display on or off. The display can be drawn to and retains all
+    /// of its memory even while off.
+    ///
+    /// The charge pump keeps running while the display is off. Use [`sleep`](Self::sleep) to
+    /// turn it off as well. Turning a sleeping display on restarts the charge pump and restores
+    /// the contrast dimmed by [`Ssd1306Config::with_dim_on_sleep`] straight away. Use
+    /// [`wake`](Self::wake) to let the display settle first.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306, Ssd1306Config};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_config(Ssd1306Config::new().with_dim_on_sleep(true))
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// display.sleep().unwrap();
+    /// display.set_display_on(true).unwrap();
+    /// // The contrast is already back to the current brightness, so this sends nothing
+    /// display.set_brightness(Brightness::NORMAL).unwrap();
+    /// drop(display);
+    ///
+    /// // Charge pump on, display on, contrast 0x5F
+    /// assert!(log.commands().ends_with(&[0x8D, 0x14, 0xAF, 0x81, 0x5F]));
+    /// ```
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error> {
+        if let Some(commands) = self.display_on_commands(on, true)? {
+            commands.send(&mut self.interface)?;
+            self.power = if on { PowerState::On } else { PowerState::Off };
         }
+
         Ok(())
     }
 
-    /// Change the display brightness.
-    pub fn set_brightness(&mut self, brightness: Brightness) -> Result<(), DisplayError> {
-        Command::PreChargePeriod(1, brightness.precharge).send(&mut self.interface)?;
-        Command::Contrast(brightness.contrast).send(&mut self.interface)
+    /// Put the display to sleep, turning off the display and its charge pump.
+    ///
+    /// Display RAM and settings are kept, so [`wake`](Self::wake) brings back the same image.
+    /// If [`Ssd1306Config::with_dim_on_sleep`] is set, the contrast is set to 0 first.
+    ///
+    /// Buffered graphics modes keep drawing to their framebuffer while the display is asleep, but
+    /// only send it once the display is awake again.
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::DelayStub;
+    /// # let mut delay = DelayStub;
+    /// use ssd1306::{emulator::Emulator, power::PowerState, prelude::*, Ssd1306, Ssd1306Config};
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .with_config(Ssd1306Config::new().with_dim_on_sleep(true))
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// display.sleep().unwrap();
+    /// assert_eq!(display.power_state(), PowerState::Asleep);
+    ///
+    /// // Only the framebuffer is updated while asleep
+    /// display.set_pixel(3, 20, true);
+    /// display.flush().unwrap();
+    ///
+    /// display.wake(&mut delay).unwrap();
+    /// display.flush().unwrap();
+    /// assert_eq!(display.power_state(), PowerState::On);
+    /// # drop(display);
+    /// assert!(emulator.is_display_on() && emulator.is_charge_pump_on());
+    /// // The contrast set by `init` is restored
+    /// assert_eq!(emulator.contrast(), 0x5F);
+    /// assert!(emulator.panel_pixel(3, 20));
+    /// ```
+    pub fn sleep(&mut self) -> Result<(), DisplayError> {
+        if let Some(commands) = self.sleep_commands()? {
+            commands.send(&mut self.interface)?;
+            self.power = PowerState::Asleep;
+        }
+
+        Ok(())
     }
 
-    /// Turn the display on or off. The display can be drawn to and retains all
-    /// of its memory even while off.
-    pub fn set_display_on(&mut self, on: bool) -> Result<(), DisplayError> {
-        Command::DisplayOn(on).send(&mut self.interface)
+    /// Wake the display up after [`sleep`](Self::sleep).
+    ///
+    /// The charge pump is started and the display turned on, then this waits the 100ms the
+    /// display needs to settle before restoring the contrast.
+    pub fn wake<DELAY>(&mut self, delay: &mut DELAY) -> Result<(), DisplayError>
+    where
+        DELAY: DelayNs,
+    {
+        if self.power == PowerState::On {
+            return Ok(());
+        }
+
+        if let Some(commands) = self.display_on_commands(true, false)? {
+            commands.send(&mut self.interface)?;
+            self.power = PowerState::On;
+        }
+        delay.delay_ms(WAKE_DELAY_MS);
+
+        self.wake_commands()?.send(&mut self.interface)
     }
 
     /// Set the position in the framebuffer of the display limiting where any sent data should be
     /// drawn. This method can be used for changing the affected area on the screen as well
     /// as (re-)setting the start point of the next `draw` call.
-    pub fn set_draw_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), DisplayError> {
-        // Command::ColumnAddress(start.0, end.0.saturating_sub(1)).send(&mut self.interface)?;
-        let _col = (start.0, end.0 - 1 );
-        let _page = (start.1, (end.1 - 1));
-
-        Command::ColumnAddress(start.0, end.0 - 1 ).send(&mut self.interface)?;
-
-        if self.addr_mode != AddrMode::Page {
-            Command::PageAddressNum(start.1, end.1 - 1 )
-                .send(&mut self.interface)?;
-        }
+    ///
+    /// `start` is inclusive and `end` exclusive, both in pixels. Rows are rounded to the 8px page
+    /// that contains them.
+    ///
+    /// Returns [`Error::InvalidArea`] if the area is empty or extends past display RAM.
+    pub fn set_draw_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), Error> {
+        self.draw_area_commands(start, end)?
+            .send(&mut self.interface)?;
 
         Ok(())
     }
 
     /// Set the column address in the framebuffer of the display where any sent data should be
     /// drawn.
-    // pub fn set_column(&mut self, column: u8) -> Result<(), DisplayError> {
-    //     Command::ColStart(column).send(&mut self.interface)
-    // }
-    pub fn set_column(&mut self, column: u8) -> Result<(), DisplayError> {
-        Command::ColStart(column).send(&mut self.interface)
+    ///
+    /// `column` is a display RAM column, from 0 up to the controller's
+    /// [`ram_columns`](Controller::ram_columns), so it includes the panel's column offset.
+    pub fn set_column(&mut self, column: u8) -> Result<(), Error> {
+        Ok(self.command(Command::ColStart(column))?)
     }
-     /// Set the column address individually
-    // pub fn set_column(&mut self, column: u8) -> Result<(), DisplayError> {
-    //     Command::ColStart(column).send(&mut self.interface)
-    // }
-    pub fn set_column_2(&mut self, column: u8) -> Result<(), DisplayError> {
-        Command::UpperColStart(column).send(&mut self.interface)?;
-        Command::LowerColStart(column).send(&mut self.interface)
+
+    /// Set the column address by sending its upper nibble before its lower nibble.
+    ///
+    /// This takes a full display RAM column like [`set_column`](Self::set_column). It used to take
+    /// a column without the SH1106's offset of 2 and add it to the upper nibble.
+    #[deprecated(
+        since = "0.9.0",
+        note = "use `set_column`, which takes the same column"
+    )]
+    pub fn set_column_2(&mut self, column: u8) -> Result<(), Error> {
+        Ok(self.commands([
+            Command::UpperColStart(column >> 4),
+            Command::LowerColStart(column),
+        ])?)
     }
 
     /// Set the page address (row 8px high) in the framebuffer of the display where any sent data
     /// should be drawn.
     ///
     /// Note that the parameter is in pixels, but the page will be set to the start of the 8px
-    /// row which contains the passed-in row.
-    pub fn set_row(&mut self, row: u8) -> Result<(), DisplayError> {
-        Command::PageStart(row.into()).send(&mut self.interface)
+    /// row which contains the passed-in row. Returns [`Error::OutOfBounds`] for rows past the
+    /// last page of display RAM.
+    pub fn set_row(&mut self, row: u8) -> Result<(), Error> {
+        self.row_commands(row)?.send(&mut self.interface)?;
+
+        Ok(())
     }
 
     /// Set the screen pixel on/off inversion
-    pub fn set_invert(&mut self, invert: bool) -> Result<(), DisplayError> {
-        Command::Invert(invert).send(&mut self.interface)
+    pub fn set_invert(&mut self, invert: bool) -> Result<(), Error> {
+        if let Some(commands) = self.invert_commands(invert)? {
+            commands.send(&mut self.interface)?;
+            self.inverted = invert;
+        }
+
+        Ok(())
     }
 
-    fn flush_buffer_chunks(
-        interface: &mut DI,
-        buffer: &[u8],
-        disp_width: usize,
-        upper_left: (u8, u8),
-        lower_right: (u8, u8),
-    ) -> Result<(), DisplayError> {
-        // Divide by 8 since each row is actually 8 pixels tall
-        let num_pages = ((lower_right.1 - upper_left.1) / 8) as usize + 1;
+    /// Send the changed parts of a [`Framebuffer`] to the display.
+    ///
+    /// This lets rendering happen away from the display, for example in a task that doesn't own
+    /// the bus. The framebuffer must have been created with the display's size and rotation,
+    /// otherwise [`DisplayError::OutOfBoundsError`] is returned. Like
+    /// [`flush`](Ssd1306::flush), only the parts changed since the framebuffer was last flushed
+    /// are sent, and nothing is sent while the display is
+    /// [asleep](crate::power::PowerState::Asleep).
+    ///
+    /// ```rust
+    /// use ssd1306::{emulator::Emulator, prelude::*, Framebuffer, Ssd1306};
+    ///
+    /// // Render without a display
+    /// let mut framebuffer = Framebuffer::new(DisplaySize128x64, DisplayRotation::Rotate0);
+    /// framebuffer.set_pixel(10, 20, true);
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .init()
+    ///     .unwrap();
+    /// display.flush_from(&mut framebuffer).unwrap();
+    /// # drop(display);
+    ///
+    /// assert!(emulator.panel_pixel(10, 20));
+    /// ```
+    pub fn flush_from(&mut self, framebuffer: &mut Framebuffer<SIZE>) -> Result<(), DisplayError> {
+        while let Some(area) = self.next_area_from(framebuffer)? {
+            for page in area.pages() {
+                let (commands, data) =
+                    self.area_page(framebuffer.buffer().as_ref(), &area, page)?;
+                commands.send(&mut self.interface)?;
+                self.interface.send_data(U8(data))?;
+            }
+        }
 
-        // Each page is 8 bits tall, so calculate which page number to start at (rounded down) from
-        // the top of the display
-        let starting_page = (upper_left.1 / 8) as usize;
+        Ok(())
+    }
 
-        // Calculate start and end X coordinates for each page
-        let page_lower = upper_left.0 as usize;
-        let page_upper = lower_right.0 as usize;
+    /// Zero display RAM from `start` to the exclusive `end`, addressing it the way the current
+    /// addressing mode needs.
+    pub(crate) fn clear_area(&mut self, start: (u8, u8), end: (u8, u8)) -> Result<(), Error> {
+        let area = DirtyArea::ram(start, end).ok_or(Error::InvalidArea)?;
 
-        buffer
-            .chunks(disp_width)
-            .skip(starting_page)
-            .take(num_pages)
-            .map(|s| &s[page_lower..page_upper])
-            .try_for_each(|c| interface.send_data(U8(&c)))
-    }
+        for page in area.pages() {
+            self.area_page_commands(&area, page)?
+                .send(&mut self.interface)?;
+            self.draw(area.zeros())?;
+        }
 
-   
-    fn flush_buffer_chunks_2(
-        &mut self, 
-        buffer: &[u8],
-        disp_width: usize,
-        upper_left: (u8, u8),
-        lower_right: (u8, u8),
-    ) -> Result<(), DisplayError> {
-        // Divide by 8 since each row is actually 8 pixels tall
-        let num_pages = ((lower_right.1 - upper_left.1) / 8) as usize + 1;
-
-        // Each page is 8 bits tall, so calculate which page number to start at (rounded down) from
-        // the top of the display
-        let starting_page = (upper_left.1 / 8) as usize;
-
-        // Calculate start and end X coordinates for each page
-        let page_lower = upper_left.0 as usize;
-        let page_upper = lower_right.0 as usize;
-
-        for (i , row) in buffer
-            .chunks(disp_width)
-            .skip(starting_page)
-            .take(num_pages)
-            .map(|s| &s[page_lower..page_upper])
-            .enumerate()
-            {
-                self.set_row(i.try_into().unwrap())?;
-                self.set_column_2(0)?;                
-                self.interface.send_data(U8(row))?
-            }
         Ok(())
     }
+
+    /// Send a command, rejecting it if the controller doesn't support it.
+    fn command(&mut self, command: Command) -> Result<(), DisplayError> {
+        self.commands([command])
+    }
+
+    /// Send several commands in as few transactions as possible. Nothing is sent if the
+    /// controller doesn't support one of them.
+    fn commands<const N: usize>(&mut self, commands: [Command; N]) -> Result<(), DisplayError> {
+        self.checked_commands::<N>(&commands)?
+            .send(&mut self.interface)
+    }
+}
+
+/// Passes commands sent by [`DisplaySize::configure`] on to the interface, rejecting any the
+/// controller doesn't support.
+pub(crate) struct CheckedInterface<'a, DI> {
+    pub(crate) interface: &'a mut DI,
+    pub(crate) controller: Controller,
+}
+
+impl<DI> WriteOnlyDataCommand for CheckedInterface<'_, DI>
+where
+    DI: WriteOnlyDataCommand,
+{
+    fn send_commands(&mut self, cmd: DataFormat<'_>) -> Result<(), DisplayError> {
+        if let U8(bytes) = &cmd {
+            self.controller.check_encoded(bytes)?;
+        }
+
+        self.interface.send_commands(cmd)
+    }
+
+    fn send_data(&mut self, buf: DataFormat<'_>) -> Result<(), DisplayError> {
+        self.interface.send_data(buf)
+    }
 }
 
 // SPI-only reset
 impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE> {
     /// Reset the display.
+    ///
+    /// This resets the display's registers, so a display that was already initialised must be
+    /// initialised again with [`DisplayConfig::init`] afterwards.
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::{DelayStub, PinStub, SpiStub};
+    /// # let (spi, dc, mut rst, mut delay) = (SpiStub, PinStub, PinStub, DelayStub);
+    /// use ssd1306::{prelude::*, Ssd1306};
+    ///
+    /// let interface = SPIInterface::new(spi, dc);
+    /// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0);
+    /// display.reset(&mut rst, &mut delay).unwrap();
+    /// let display = display.init().unwrap();
+    /// ```
     pub fn reset<RST, DELAY>(
         &mut self,
         rst: &mut RST,
@@ -476,12 +1241,12 @@ impl<DI, SIZE, MODE> Ssd1306<DI, SIZE, MODE> {
     ) -> Result<(), Error<Infallible, RST::Error>>
     where
         RST: OutputPin,
-        DELAY: DelayMs<u8>,
+        DELAY: DelayNs,
     {
         fn inner_reset<RST, DELAY>(rst: &mut RST, delay: &mut DELAY) -> Result<(), RST::Error>
         where
             RST: OutputPin,
-            DELAY: DelayMs<u8>,
+            DELAY: DelayNs,
         {
             rst.set_high()?;
             delay.delay_ms(1);
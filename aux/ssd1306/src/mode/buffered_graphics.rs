@@ -2,11 +2,62 @@
 
 use crate::{
     command::AddrMode,
-    rotation::DisplayRotation,
+    controller::Controller,
+    framebuffer::{DirtyArea, Framebuffer},
+    power::PowerState,
+    rotation::{DisplayRotation, Orientation},
     size::{DisplaySize, NewZeroed},
     Ssd1306,
 };
-use display_interface::{DisplayError, WriteOnlyDataCommand};
+use core::fmt;
+use display_interface::{DataFormat::U8, DisplayError, WriteOnlyDataCommand};
+
+/// Whether a [`BufferedGraphicsMode`] keeps a copy of what was last sent to the display.
+pub trait ShadowMode<SIZE: DisplaySize> {
+    /// Storage for the copy. It is ignored if it is smaller than the buffer.
+    type Buffer: AsMut<[u8]> + NewZeroed;
+}
+
+/// Flush every changed area of the buffer. This is the default.
+#[derive(Debug, Copy, Clone)]
+pub struct NoShadow;
+
+impl<SIZE> ShadowMode<SIZE> for NoShadow
+where
+    SIZE: DisplaySize,
+{
+    type Buffer = [u8; 0];
+}
+
+/// Keep a copy of what was last sent to the display and only flush the bytes that differ from
+/// it. This doubles the memory used by the mode.
+#[derive(Debug, Copy, Clone)]
+pub struct Shadowed;
+
+impl<SIZE> ShadowMode<SIZE> for Shadowed
+where
+    SIZE: DisplaySize,
+{
+    type Buffer = SIZE::Buffer;
+}
+
+/// How far the shadow copy can be trusted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ShadowState {
+    /// Display RAM may differ from the copy, so the next flush sends everything
+    Stale,
+    /// A flush of everything is in progress
+    Syncing,
+    /// The copy matches display RAM
+    Synced,
+}
+
+/// The area a flush is sending and the next buffer page of it to send.
+#[derive(Debug, Copy, Clone)]
+struct FlushProgress {
+    area: DirtyArea,
+    page: u8,
+}
 
 /// Buffered graphics mode.
 ///
@@ -14,38 +65,262 @@ use display_interface::{DisplayError, WriteOnlyDataCommand};
 /// buffer is drawn to by [`set_pixel`](Ssd1306::set_pixel) commands or
 /// [`embedded-graphics`](https://docs.rs/embedded-graphics) commands. The display can then be
 /// updated using the [`flush`](Ssd1306::flush) method.
-#[derive(Clone, Debug)]
-pub struct BufferedGraphicsMode<SIZE>
+///
+/// With [`Shadowed`], created by
+/// [`into_shadowed_graphics_mode`](Ssd1306::into_shadowed_graphics_mode), the mode also keeps a
+/// copy of what was last sent and flushes only the bytes that changed. Redrawing a whole frame
+/// then costs no more than the pixels that actually differ. The copy assumes display RAM is only
+/// written by `flush`; it is resent in full after [`DisplayConfig::init`] or a failed flush.
+pub struct BufferedGraphicsMode<SIZE, SHADOW = NoShadow>
+where
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    framebuffer: Framebuffer<SIZE>,
+    shadow: SHADOW::Buffer,
+    shadow_state: ShadowState,
+    progress: Option<FlushProgress>,
+}
+
+// Derived impls would require `SIZE` itself to implement the traits instead of its buffer
+impl<SIZE, SHADOW> Clone for BufferedGraphicsMode<SIZE, SHADOW>
+where
+    SIZE: DisplaySize,
+    SIZE::Buffer: Clone,
+    SHADOW: ShadowMode<SIZE>,
+    SHADOW::Buffer: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            framebuffer: self.framebuffer.clone(),
+            shadow: self.shadow.clone(),
+            shadow_state: self.shadow_state,
+            progress: self.progress,
+        }
+    }
+}
+
+impl<SIZE, SHADOW> fmt::Debug for BufferedGraphicsMode<SIZE, SHADOW>
 where
     SIZE: DisplaySize,
+    SIZE::Buffer: fmt::Debug,
+    SHADOW: ShadowMode<SIZE>,
+    SHADOW::Buffer: fmt::Debug,
 {
-    buffer: SIZE::Buffer,
-    min_x: u8,
-    max_x: u8,
-    min_y: u8,
-    max_y: u8,
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferedGraphicsMode")
+            .field("framebuffer", &self.framebuffer)
+            .field("shadow", &self.shadow)
+            .field("shadow_state", &self.shadow_state)
+            .field("progress", &self.progress)
+            .finish()
+    }
 }
 
-impl<SIZE> BufferedGraphicsMode<SIZE>
+impl<SIZE, SHADOW> BufferedGraphicsMode<SIZE, SHADOW>
 where
     SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
 {
     /// Create a new buffered graphics mode instance.
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(size: &SIZE, orientation: Orientation) -> Self {
         Self {
-            buffer: NewZeroed::new_zeroed(),
-            min_x: 255,
-            max_x: 0,
-            min_y: 255,
-            max_y: 0,
+            framebuffer: Framebuffer::for_size(size, orientation),
+            shadow: NewZeroed::new_zeroed(),
+            shadow_state: ShadowState::Stale,
+            progress: None,
+        }
+    }
+
+    pub(crate) fn framebuffer(&self) -> &Framebuffer<SIZE> {
+        &self.framebuffer
+    }
+
+    pub(crate) fn framebuffer_mut(&mut self) -> &mut Framebuffer<SIZE> {
+        &mut self.framebuffer
+    }
+
+    /// Follow a change of the display orientation. The buffer is laid out again for the new
+    /// orientation and sent in full by the next flush.
+    pub(crate) fn set_orientation(&mut self, orientation: Orientation) {
+        // Changing the orientation moves the RAM pointer and column offset of a partly sent area
+        self.cancel_flush();
+
+        // Flipping segment remapping moves the image to the other side of display RAM, unless
+        // the panel is centred in it, so the shadow no longer matches what is shown
+        let remap = |orientation: Orientation| orientation.remap_and_com_dir().0;
+        if remap(orientation) != remap(self.framebuffer.orientation()) {
+            self.invalidate_shadow();
         }
+
+        self.framebuffer.set_orientation(orientation);
+    }
+
+    /// Stop trusting the shadow copy, so the next flush sends the whole buffer.
+    pub(crate) fn invalidate_shadow(&mut self) {
+        self.cancel_flush();
+        self.shadow_state = ShadowState::Stale;
+    }
+
+    /// Stop sending the area a [`FlushJob`] was part way through. Its remaining pages are marked
+    /// as changed again so the next flush starts over with them.
+    pub(crate) fn cancel_flush(&mut self) {
+        let FlushProgress { area, page } = match self.progress.take() {
+            Some(progress) => progress,
+            None => return,
+        };
+
+        for page in page..=area.lower_right.1 / 8 {
+            self.framebuffer
+                .mark(usize::from(page), area.upper_left.0, area.lower_right.0 - 1);
+        }
+    }
+
+    /// The area being flushed and the next page of it to send, taking a new area when the last
+    /// one is done. `None` when the display is up to date.
+    fn flush_progress(
+        &mut self,
+        size: &SIZE,
+        controller: Controller,
+        merge: bool,
+    ) -> Option<FlushProgress> {
+        if self.progress.is_none() {
+            self.progress =
+                self.next_dirty_area(size, controller, merge)
+                    .map(|area| FlushProgress {
+                        area,
+                        page: area.upper_left.1 / 8,
+                    });
+        }
+
+        self.progress
+    }
+
+    /// Move on to the next page of the area being flushed.
+    pub(crate) fn page_sent(&mut self) {
+        if let Some(progress) = &mut self.progress {
+            progress.page += 1;
+            if progress.page > progress.area.lower_right.1 / 8 {
+                self.progress = None;
+            }
+        }
+    }
+
+    /// Whether a flush has nothing left to send. Unlike [`flush_progress`](Self::flush_progress)
+    /// this doesn't take the next area, so pixels drawn before the next step are still sent by
+    /// it.
+    pub(crate) fn flush_done(&mut self) -> bool {
+        if self.progress.is_some() || self.framebuffer.is_dirty() {
+            return false;
+        }
+
+        match self.shadow_state {
+            ShadowState::Stale => !self.shadowed(),
+            ShadowState::Syncing => {
+                self.shadow_state = ShadowState::Synced;
+                true
+            }
+            ShadowState::Synced => true,
+        }
+    }
+
+    /// Copy one page of an area into the shadow copy after it has been sent, so the copy holds
+    /// the bytes that actually reached the display even if the buffer changed while the area was
+    /// being flushed.
+    pub(crate) fn shadow_page(&mut self, area: &DirtyArea, page: u8) {
+        if !self.shadowed() {
+            return;
+        }
+
+        if let Some(columns) = area.page_columns(page) {
+            if let (Some(sent), Some(copy)) = (
+                self.framebuffer.buffer().as_ref().get(columns.clone()),
+                self.shadow.as_mut().get_mut(columns),
+            ) {
+                copy.copy_from_slice(sent);
+            }
+        }
+    }
+
+    /// Whether there is a shadow copy. A copy smaller than the buffer can't be used.
+    fn shadowed(&mut self) -> bool {
+        self.shadow.as_mut().len() >= self.framebuffer.buffer().as_ref().len()
+    }
+
+    /// Mark everything as changed and stop trusting the shadow copy, so the next flush sends the
+    /// whole buffer.
+    pub(crate) fn mark_all(&mut self) {
+        self.invalidate_shadow();
+        self.framebuffer.mark_all();
+    }
+
+    #[cfg(feature = "graphics")]
+    /// Take the area covering a rectangle given as `(x, y, width, height)` in display
+    /// coordinates, whether it has changed or not. See [`Framebuffer::area_of`].
+    pub(crate) fn take_area(
+        &mut self,
+        size: &SIZE,
+        controller: Controller,
+        rect: (u32, u32, u32, u32),
+    ) -> Option<DirtyArea> {
+        // Addressing another area would cut a partly sent one short
+        self.cancel_flush();
+
+        self.framebuffer.area_of(size, controller, rect)
+    }
+
+    /// Take the next area that needs to be sent, or `None` when the display is up to date. See
+    /// [`Framebuffer::next_dirty_area`].
+    pub(crate) fn next_dirty_area(
+        &mut self,
+        size: &SIZE,
+        controller: Controller,
+        merge: bool,
+    ) -> Option<DirtyArea> {
+        let shadowed = self.shadowed();
+        if shadowed && self.shadow_state == ShadowState::Stale {
+            self.framebuffer.mark_all();
+            self.shadow_state = ShadowState::Syncing;
+        }
+
+        // Until everything has been sent once the copy doesn't match display RAM
+        let diff = shadowed && self.shadow_state == ShadowState::Synced;
+        let shadow = Some(self.shadow.as_mut())
+            .filter(|_| diff)
+            .map(|shadow| &*shadow);
+
+        let area = self
+            .framebuffer
+            .next_dirty_area(size, controller, merge, shadow);
+        if area.is_none() && self.shadow_state == ShadowState::Syncing {
+            self.shadow_state = ShadowState::Synced;
+        }
+
+        area
     }
 }
 
-impl<DI, SIZE> DisplayConfig for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    /// The area being flushed and the next page of it to send, taking a new area when the last
+    /// one is done. `None` when the display is up to date.
+    pub(crate) fn next_flush_page(&mut self) -> Option<(DirtyArea, u8)> {
+        let merge = self.addr_mode == AddrMode::Horizontal;
+
+        self.mode
+            .flush_progress(&self.size, self.controller, merge)
+            .map(|FlushProgress { area, page }| (area, page))
+    }
+}
+
+impl<DI, SIZE, SHADOW> DisplayConfig for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
 {
     type Error = DisplayError;
 
@@ -53,30 +328,33 @@ where
     ///
     /// This method resets the cursor but does not clear the screen.
     fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
-        self.set_rotation(rot)
+        Ok(self.set_rotation(rot)?)
+    }
+
+    /// Set the display orientation
+    ///
+    /// This method does not clear the screen.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation)?)
     }
 
     /// Initialise and clear the display in graphics mode.
     fn init(&mut self) -> Result<(), DisplayError> {
+        self.mode.invalidate_shadow();
         self.clear_impl(false);
         // self.init_with_addr_mode(AddrMode::Horizontal)
-        self.init_with_addr_mode(AddrMode::Horizontal)
+        self.init_with_addr_mode(self.controller.area_addr_mode())
     }
 }
 
-impl<DI, SIZE> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW> Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
 {
     fn clear_impl(&mut self, value: bool) {
-        self.mode.buffer.as_mut().fill(if value { 0xff } else { 0 });
-
-        let (width, height) = self.dimensions();
-        self.mode.min_x = 0;
-        self.mode.max_x = width - 1;
-        self.mode.min_y = 0;
-        self.mode.max_y = height - 1;
+        self.mode.framebuffer_mut().fill(value);
     }
 
     /// Clear the underlying framebuffer. You need to call `disp.flush()` for any effect on the screen.
@@ -86,129 +364,369 @@ where
 
     /// Write out data to a display.
     ///
-    /// This only updates the parts of the display that have changed since the last flush.
+    /// This only updates the parts of the display that have changed since the last flush. Each
+    /// page keeps track of its own changed columns, so changes far apart don't resend everything
+    /// in between. If a flush fails, the next one sends the changes that were not sent yet
+    /// again, or everything in [`Shadowed`] mode.
+    ///
+    /// Nothing is sent while the display is [asleep](crate::power::PowerState::Asleep). The
+    /// changes are kept and sent by the first flush after the display wakes up.
+    ///
+    /// Use [`flush_job`](Ssd1306::flush_job) to send the changes a page at a time instead.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// // `init` clears the buffer, so this sends all of it
+    /// display.flush().unwrap();
+    ///
+    /// // Opposite corners
+    /// display.set_pixel(0, 0, true);
+    /// display.set_pixel(127, 63, true);
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// assert_eq!(log.data_len(), 1024 + 2);
+    /// ```
+    ///
+    /// In [`Shadowed`] mode only the bytes that differ from what was last sent are updated, so
+    /// redrawing the same pixels sends nothing:
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_shadowed_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    ///
+    /// // Clear and redraw the same frame
+    /// display.clear_buffer();
+    /// display.set_pixel(0, 0, true);
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// assert_eq!(log.data_len(), 1024);
+    /// ```
     pub fn flush(&mut self) -> Result<(), DisplayError> {
-        // Nothing to do if no pixels have changed since the last update
-        if self.mode.max_x < self.mode.min_x || self.mode.max_y < self.mode.min_y {
-            return Ok(());
-        }
-
-        let (width, height) = self.dimensions();
+        self.flush_job().finish()
+    }
 
-        // Determine which bytes need to be sent
-        let disp_min_x = self.mode.min_x;
-        let disp_min_y = self.mode.min_y;
+    /// Start sending the changed parts of the buffer a page at a time.
+    ///
+    /// Each [`FlushJob::step`] sends at most one page of the display, so a main loop or timer
+    /// interrupt can spread a frame over many short slices instead of blocking in
+    /// [`flush`](Ssd1306::flush). How far the flush got is kept by the display, so a job can be
+    /// dropped between steps and a new one picks up where it left off. `flush` finishes a flush
+    /// started by a job.
+    ///
+    /// The buffer can still be drawn to between steps; pixels changed after their page was sent
+    /// are sent by the next flush. Commands that move the display RAM pointer, like
+    /// [`draw`](Ssd1306::draw) or [`set_draw_area`](Ssd1306::set_draw_area), should not be sent
+    /// until the job is done.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// // `init` clears the buffer, so all 8 pages are sent
+    /// let mut steps = 1;
+    /// while !display.flush_job().step().unwrap() {
+    ///     steps += 1;
+    ///     // Poll timers, sample inputs...
+    /// }
+    /// assert_eq!(steps, 8);
+    ///
+    /// // Nothing left to send
+    /// assert!(display.flush_job().step().unwrap());
+    /// ```
+    ///
+    /// In [`Shadowed`] mode the copy of what was sent is updated a page at a time, so pixels
+    /// drawn into pages a job hasn't reached yet are compared with what the display really shows:
+    ///
+    /// ```rust
+    /// use ssd1306::{command::AddrMode, emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_shadowed_graphics_mode()
+    ///     .init_with_addr_mode(AddrMode::Horizontal)
+    ///     .unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // One pixel in each of the first three pages, sent as one area
+    /// display.set_pixel(0, 0, true);
+    /// display.set_pixel(0, 8, true);
+    /// display.set_pixel(0, 16, true);
+    /// display.flush_job().step().unwrap();
+    ///
+    /// // Turned on before its page is sent and off again after
+    /// display.set_pixel(0, 9, true);
+    /// display.flush_job().step().unwrap();
+    /// display.set_pixel(0, 9, false);
+    /// display.flush().unwrap();
+    ///
+    /// assert_eq!(display.get_pixel(0, 9), Some(false));
+    /// # drop(display);
+    /// assert!(!emulator.panel_pixel(0, 9));
+    /// assert!(emulator.panel_pixel(0, 8));
+    /// ```
+    pub fn flush_job(&mut self) -> FlushJob<'_, DI, SIZE, SHADOW> {
+        FlushJob { display: self }
+    }
 
-        let (disp_max_x, disp_max_y) = match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (
-                (self.mode.max_x + 1).min(width),
-                (self.mode.max_y | 7).min(height),
-            ),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (
-                (self.mode.max_x | 7).min(width),
-                (self.mode.max_y + 1).min(height),
-            ),
+    /// Send the next page of the area being flushed and return whether the flush is done.
+    fn flush_page(&mut self) -> Result<bool, DisplayError> {
+        let (area, page) = match self.next_flush_page() {
+            Some(next) => next,
+            None => return Ok(true),
         };
 
-        self.mode.min_x = 255;
-        self.mode.max_x = 0;
-        self.mode.min_y = 255;
-        self.mode.max_y = 0;
-
-        // Tell the display to update only the part that has changed
-        let offset_x = match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
-            DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
-                // If segment remapping is flipped, we need to calculate
-                // the offset from the other edge of the display.
-                SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
-            }
+        self.send_area_page(area, page)?;
+        self.mode.page_sent();
+
+        Ok(self.mode.flush_done())
+    }
+
+    /// Send one page of an area.
+    fn send_area_page(&mut self, area: DirtyArea, page: u8) -> Result<(), DisplayError> {
+        let (commands, data) =
+            self.area_page(self.mode.framebuffer().buffer().as_ref(), &area, page)?;
+        commands.send(&mut self.interface)?;
+        self.interface.send_data(U8(data))?;
+        self.mode.shadow_page(&area, page);
+
+        Ok(())
+    }
+
+    /// Send a rectangle of the buffer to the display, whether it has changed or not.
+    ///
+    /// The rectangle is given in display coordinates and clipped to the display. Changes outside
+    /// of it are left for the next [`flush`](Ssd1306::flush). A flush started by a
+    /// [`FlushJob`] is cut short and its remaining changes are sent by the next flush. Nothing is
+    /// sent while the display is [asleep](crate::power::PowerState::Asleep).
+    ///
+    /// ```rust
+    /// use embedded_graphics::{prelude::*, primitives::Rectangle};
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_shadowed_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Nothing changed, but a 10x10 square is sent anyway. It covers two pages.
+    /// display
+    ///     .flush_area(&Rectangle::new(Point::new(20, 4), Size::new(10, 10)))
+    ///     .unwrap();
+    /// # drop(display);
+    ///
+    /// assert_eq!(log.data_len(), 1024 + 2 * 10);
+    /// ```
+    #[cfg(feature = "graphics")]
+    pub fn flush_area(&mut self, area: &Rectangle) -> Result<(), DisplayError> {
+        if self.power_state() == PowerState::Asleep {
+            return Ok(());
+        }
+
+        let rect = match self.mode.framebuffer().clip(area) {
+            Some(rect) => rect,
+            None => return Ok(()),
+        };
+        let area = match self.mode.take_area(&self.size, self.controller, rect) {
+            Some(area) => area,
+            None => return Ok(()),
         };
 
-        match self.rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                self.set_draw_area(
-                    (disp_min_x + offset_x, disp_min_y + SIZE::OFFSETY),
-                    (disp_max_x + offset_x, disp_max_y + SIZE::OFFSETY),
-                )?;
-
-                Self::flush_buffer_chunks(
-                    &mut self.interface,
-                    self.mode.buffer.as_mut(),
-                    width as usize,
-                    (disp_min_x, disp_min_y),
-                    (disp_max_x, disp_max_y),
-                )
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                self.set_draw_area(
-                    (disp_min_y + offset_x, disp_min_x + SIZE::OFFSETY),
-                    (disp_max_y + offset_x, disp_max_x + SIZE::OFFSETY),
-                )?;
-
-                Self::flush_buffer_chunks(
-                    &mut self.interface,
-                    self.mode.buffer.as_mut(),
-                    height as usize,
-                    (disp_min_y, disp_min_x),
-                    (disp_max_y, disp_max_x),
-                )
-            }
+        let result = area
+            .pages()
+            .try_for_each(|page| self.send_area_page(area, page));
+        if result.is_err() {
+            self.mode.invalidate_shadow();
         }
+
+        result
+    }
+
+    /// Send the whole buffer to the display, whether it has changed or not.
+    ///
+    /// This repaints the display after its RAM was lost or corrupted, for example by a bus
+    /// glitch or after the panel was reset or plugged back in. The display must still be
+    /// initialised again first if its registers were reset. Nothing is sent while the display
+    /// is [asleep](crate::power::PowerState::Asleep); the next flush after it wakes up sends
+    /// everything.
+    ///
+    /// ```rust
+    /// use ssd1306::{prelude::*, test_helpers::CommandLog, Ssd1306};
+    ///
+    /// let mut log = CommandLog::new();
+    /// let mut display = Ssd1306::new(&mut log, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_shadowed_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    /// display.flush().unwrap();
+    ///
+    /// // Nothing changed, so this sends nothing
+    /// display.flush().unwrap();
+    /// // But this sends everything again
+    /// display.flush_all().unwrap();
+    /// # drop(display);
+    ///
+    /// assert_eq!(log.data_len(), 2 * 1024);
+    /// ```
+    pub fn flush_all(&mut self) -> Result<(), DisplayError> {
+        self.mode.mark_all();
+        self.flush()
     }
 
     /// Turn a pixel on or off. A non-zero `value` is treated as on, `0` as off. If the X and Y
     /// coordinates are out of the bounds of the display, this method call is a noop.
     pub fn set_pixel(&mut self, x: u32, y: u32, value: bool) {
-        let value = value as u8;
-        let rotation = self.rotation;
+        self.mode.framebuffer_mut().set_pixel(x, y, value);
+    }
 
-        let (idx, bit) = match rotation {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                let idx = ((y as usize) / 8 * SIZE::WIDTH as usize) + (x as usize);
-                let bit = y % 8;
+    /// Whether a pixel in the buffer is on, or `None` if the coordinates are outside the display.
+    ///
+    /// ```rust
+    /// # use ssd1306::test_helpers::StubInterface;
+    /// # let interface = StubInterface;
+    /// use ssd1306::{prelude::*, Ssd1306};
+    ///
+    /// let mut display = Ssd1306::new(interface, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// // XOR a cursor
+    /// let on = display.get_pixel(10, 20).unwrap();
+    /// display.set_pixel(10, 20, !on);
+    ///
+    /// assert_eq!(display.get_pixel(10, 20), Some(true));
+    /// assert_eq!(display.get_pixel(128, 0), None);
+    /// ```
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<bool> {
+        self.mode.framebuffer().get_pixel(x, y)
+    }
 
-                (idx, bit)
-            }
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                let idx = ((x as usize) / 8 * SIZE::WIDTH as usize) + (y as usize);
-                let bit = x % 8;
+    /// The page-packed pixel buffer. See [`Framebuffer::buffer`] for its layout.
+    pub fn buffer(&self) -> &SIZE::Buffer {
+        self.mode.framebuffer().buffer()
+    }
 
-                (idx, bit)
-            }
-        };
+    /// The page-packed pixel buffer for writing. See [`Framebuffer::buffer`] for its layout.
+    ///
+    /// The whole display is marked as changed, so the next [`flush`](Self::flush) sends all of
+    /// it. With [`Shadowed`] it sends only the bytes that actually changed.
+    ///
+    /// ```rust
+    /// use ssd1306::{emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// let mut emulator = Emulator::new(DisplaySize128x64);
+    /// let mut display = Ssd1306::new(&mut emulator, DisplaySize128x64, DisplayRotation::Rotate0)
+    ///     .into_buffered_graphics_mode()
+    ///     .init()
+    ///     .unwrap();
+    ///
+    /// // Column 3 of the second page, rows 8 to 15, top pixel only
+    /// display.buffer_mut()[128 + 3] = 0b0000_0001;
+    /// display.flush().unwrap();
+    /// # drop(display);
+    ///
+    /// assert!(emulator.panel_pixel(3, 8));
+    /// assert!(!emulator.panel_pixel(3, 9));
+    /// ```
+    pub fn buffer_mut(&mut self) -> &mut SIZE::Buffer {
+        self.mode.framebuffer_mut().buffer_mut()
+    }
+}
+
+/// A flush of a [`BufferedGraphicsMode`] display that sends one page per
+/// [`step`](FlushJob::step), created by [`Ssd1306::flush_job`].
+pub struct FlushJob<'a, DI, SIZE, SHADOW = NoShadow>
+where
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    display: &'a mut Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>,
+}
 
-        if let Some(byte) = self.mode.buffer.as_mut().get_mut(idx) {
-            // Keep track of max and min values
-            self.mode.min_x = self.mode.min_x.min(x as u8);
-            self.mode.max_x = self.mode.max_x.max(x as u8);
+impl<DI, SIZE, SHADOW> fmt::Debug for FlushJob<'_, DI, SIZE, SHADOW>
+where
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlushJob").finish_non_exhaustive()
+    }
+}
 
-            self.mode.min_y = self.mode.min_y.min(y as u8);
-            self.mode.max_y = self.mode.max_y.max(y as u8);
+impl<DI, SIZE, SHADOW> FlushJob<'_, DI, SIZE, SHADOW>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    /// Send the next page of changes. Returns `true` once everything has been sent.
+    ///
+    /// Nothing is sent while the display is [asleep](crate::power::PowerState::Asleep), and the
+    /// job reports that it is done. The changes are sent after the display wakes up.
+    ///
+    /// In [`Shadowed`] mode changed pixels may turn out to match what the display already shows.
+    /// Then a step sends nothing and reports that the job is done.
+    pub fn step(&mut self) -> Result<bool, DisplayError> {
+        if self.display.power_state() == PowerState::Asleep {
+            return Ok(true);
+        }
 
-            // Set pixel value in byte
-            // Ref this comment https://stackoverflow.com/questions/47981/how-do-you-set-clear-and-toggle-a-single-bit#comment46654671_47990
-            *byte = *byte & !(1 << bit) | (value << bit)
+        let result = self.display.flush_page();
+        if result.is_err() {
+            self.display.mode.invalidate_shadow();
         }
+
+        result
+    }
+
+    /// Send everything that is left.
+    pub fn finish(mut self) -> Result<(), DisplayError> {
+        while !self.step()? {}
+
+        Ok(())
     }
 }
 
 #[cfg(feature = "graphics")]
 use embedded_graphics_core::{
     draw_target::DrawTarget,
-    geometry::Size,
-    geometry::{Dimensions, OriginDimensions},
+    geometry::{OriginDimensions, Point, Size},
+    image::GetPixel,
     pixelcolor::BinaryColor,
+    primitives::Rectangle,
     Pixel,
 };
 
 use super::DisplayConfig;
 
 #[cfg(feature = "graphics")]
-impl<DI, SIZE> DrawTarget for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW> DrawTarget for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
 {
     type Color = BinaryColor;
     type Error = DisplayError;
@@ -217,16 +735,58 @@ where
     where
         I: IntoIterator<Item = Pixel<Self::Color>>,
     {
-        let bb = self.bounding_box();
+        self.mode
+            .framebuffer_mut()
+            .draw_iter(pixels)
+            .map_err(|never| match never {})
+    }
 
-        pixels
-            .into_iter()
-            .filter(|Pixel(pos, _color)| bb.contains(*pos))
-            .for_each(|Pixel(pos, color)| {
-                self.set_pixel(pos.x as u32, pos.y as u32, color.is_on())
-            });
+    /// Fills whole page bytes at a time, so filled rectangles, text backgrounds and horizontal or
+    /// vertical lines drawn as rectangles cost a few byte operations per page.
+    ///
+    /// ```rust
+    /// use embedded_graphics::{prelude::*, pixelcolor::BinaryColor, primitives::Rectangle};
+    /// use ssd1306::{emulator::Emulator, prelude::*, Ssd1306};
+    ///
+    /// let mut filled = Emulator::new(DisplaySize128x64);
+    /// let mut drawn = Emulator::new(DisplaySize128x64);
+    ///
+    /// // Partly off the display
+    /// let area = Rectangle::new(Point::new(-5, 3), Size::new(10, 125));
+    ///
+    /// for (emulator, fill) in [(&mut filled, true), (&mut drawn, false)] {
+    ///     let mut display = Ssd1306::new(emulator, DisplaySize128x64, DisplayRotation::Rotate90)
+    ///         .into_buffered_graphics_mode()
+    ///         .init()
+    ///         .unwrap();
+    ///
+    ///     if fill {
+    ///         display.fill_solid(&area, BinaryColor::On).unwrap();
+    ///     } else {
+    ///         let pixels = area.points().map(|point| Pixel(point, BinaryColor::On));
+    ///         display.draw_iter(pixels).unwrap();
+    ///     }
+    ///     display.flush().unwrap();
+    /// }
+    ///
+    /// assert_eq!(filled.gddram(), drawn.gddram());
+    /// assert!(filled.gddram().iter().flatten().any(|&byte| byte != 0));
+    /// ```
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.mode
+            .framebuffer_mut()
+            .fill_solid(area, color)
+            .map_err(|never| match never {})
+    }
 
-        Ok(())
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.mode
+            .framebuffer_mut()
+            .fill_contiguous(area, colors)
+            .map_err(|never| match never {})
     }
 
     fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
@@ -236,10 +796,11 @@ where
 }
 
 #[cfg(feature = "graphics")]
-impl<DI, SIZE> OriginDimensions for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE>>
+impl<DI, SIZE, SHADOW> OriginDimensions for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
 where
     DI: WriteOnlyDataCommand,
     SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
 {
     fn size(&self) -> Size {
         let (w, h) = self.dimensions();
@@ -247,3 +808,17 @@ where
         Size::new(w.into(), h.into())
     }
 }
+
+#[cfg(feature = "graphics")]
+impl<DI, SIZE, SHADOW> GetPixel for Ssd1306<DI, SIZE, BufferedGraphicsMode<SIZE, SHADOW>>
+where
+    DI: WriteOnlyDataCommand,
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    type Color = BinaryColor;
+
+    fn pixel(&self, p: Point) -> Option<BinaryColor> {
+        self.mode.framebuffer().pixel(p)
+    }
+}
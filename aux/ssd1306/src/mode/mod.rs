@@ -3,7 +3,11 @@
 mod buffered_graphics;
 mod terminal;
 
-use crate::{command::AddrMode, rotation::DisplayRotation, size::DisplaySize, Ssd1306};
+use crate::{
+    rotation::{DisplayRotation, Orientation},
+    size::DisplaySize,
+    Ssd1306,
+};
 pub use buffered_graphics::*;
 use display_interface::{DisplayError, WriteOnlyDataCommand};
 pub use terminal::*;
@@ -16,10 +20,61 @@ pub trait DisplayConfig {
     /// Set display rotation.
     fn set_rotation(&mut self, rotation: DisplayRotation) -> Result<(), Self::Error>;
 
+    /// Set display orientation.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), Self::Error>;
+
     /// Initialise and configure the display for the given mode.
+    ///
+    /// Displays are initialised by [`Ssd1306::init`], so this is only needed to initialise the
+    /// display again, for example after it has been [reset](Ssd1306::reset).
     fn init(&mut self) -> Result<(), Self::Error>;
 }
 
+/// Wraps the mode of a display that hasn't been initialised yet.
+///
+/// [`Ssd1306::new`] creates displays in this state. Only configuration methods and the
+/// `into_*_mode` conversions are available until [`Ssd1306::init`] consumes the display and
+/// returns it in the wrapped mode.
+#[derive(Debug, Copy, Clone)]
+pub struct Uninitialized<MODE>(MODE);
+
+impl<MODE> Uninitialized<MODE> {
+    pub(crate) fn new(mode: MODE) -> Self {
+        Self(mode)
+    }
+
+    pub(crate) fn into_inner(self) -> MODE {
+        self.0
+    }
+
+    pub(crate) fn inner_mut(&mut self) -> &mut MODE {
+        &mut self.0
+    }
+}
+
+/// Modes of a display that has been initialised.
+///
+/// Methods that talk to the display are only available in these modes.
+pub trait InitializedMode {
+    /// Called after the display orientation has changed, so modes that lay out pixels themselves
+    /// can follow it.
+    fn orientation_changed(&mut self, _orientation: Orientation) {}
+}
+
+impl InitializedMode for BasicMode {}
+
+impl<SIZE, SHADOW> InitializedMode for BufferedGraphicsMode<SIZE, SHADOW>
+where
+    SIZE: DisplaySize,
+    SHADOW: ShadowMode<SIZE>,
+{
+    fn orientation_changed(&mut self, orientation: Orientation) {
+        self.set_orientation(orientation);
+    }
+}
+
+impl InitializedMode for TerminalMode {}
+
 /// A mode with no additional functionality beyond that provided by the base [`Ssd1306`] struct.
 #[derive(Debug, Copy, Clone)]
 pub struct BasicMode;
@@ -30,31 +85,11 @@ where
     SIZE: DisplaySize,
 {
     /// Clear the display.
+    ///
+    /// All of display RAM is cleared, including any columns and rows the panel doesn't show.
     pub fn clear(&mut self) -> Result<(), DisplayError> {
-        let old_addr_mode = self.addr_mode;
-        if old_addr_mode != AddrMode::Horizontal {
-            self.set_addr_mode(AddrMode::Horizontal)?;
-        }
-
-        let dim = self.dimensions();
-        self.set_draw_area((0, 0), dim)?;
-
-        let num_pixels = dim.0 as u16 * dim.1 as u16;
-
-        const BITS_PER_BYTE: u16 = 8;
-        const BYTES_PER_BATCH: u16 = 64;
-        const PIXELS_PER_BATCH: u16 = BITS_PER_BYTE * BYTES_PER_BATCH;
-
-        // Not all screens have number of pixels divisible by 512, so add 1 to cover tail
-        let num_batches = num_pixels / PIXELS_PER_BATCH + 1;
-
-        for _ in 0..num_batches {
-            self.draw(&[0; BYTES_PER_BATCH as usize])?;
-        }
-
-        if old_addr_mode != AddrMode::Horizontal {
-            self.set_addr_mode(old_addr_mode)?;
-        }
+        let end = (self.controller.ram_columns(), SIZE::DRIVER_ROWS);
+        self.clear_area((0, 0), end)?;
 
         Ok(())
     }
@@ -69,11 +104,17 @@ where
 
     /// Set the display rotation.
     fn set_rotation(&mut self, rot: DisplayRotation) -> Result<(), DisplayError> {
-        self.set_rotation(rot)
+        Ok(self.set_rotation(rot)?)
+    }
+
+    /// Set the display orientation.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), DisplayError> {
+        Ok(self.set_orientation(orientation)?)
     }
 
-    /// Initialise in horizontal addressing mode.
+    /// Initialise in horizontal addressing mode, or page addressing mode if the controller
+    /// doesn't support it.
     fn init(&mut self) -> Result<(), DisplayError> {
-        self.init_with_addr_mode(AddrMode::Horizontal)
+        self.init_with_addr_mode(self.controller.area_addr_mode())
     }
 }
@@ -1,12 +1,22 @@
-use crate::{command::AddrMode, mode::DisplayConfig, rotation::DisplayRotation, size::*, Ssd1306};
-use core::{cmp::min, fmt};
+use crate::{
+    command::{AddrMode, Command, CommandBatch, Page},
+    controller::Controller,
+    mode::DisplayConfig,
+    rotation::{DisplayRotation, Orientation},
+    size::*,
+    Error, Ssd1306,
+};
+use core::{cmp::min, convert::TryFrom, fmt};
 use display_interface::{DisplayError, WriteOnlyDataCommand};
 
 /// Extends the [`DisplaySize`](crate::size::DisplaySize) trait
 /// to include number of characters that can fit on the display.
+///
+/// Terminal mode works out the number of characters from the display size, so it works with any
+/// [`DisplaySize`] and this trait doesn't need to be implemented for new sizes.
 pub trait TerminalDisplaySize: DisplaySize {
     /// The number of characters that can fit on the display at once (w * h / (8 * 8))
-    const CHAR_NUM: u8;
+    const CHAR_NUM: u8 = (Self::WIDTH as u16 * Self::HEIGHT as u16 / 64) as u8;
 }
 
 impl TerminalDisplaySize for DisplaySize128x64 {
@@ -29,11 +39,34 @@ impl TerminalDisplaySize for DisplaySize64x48 {
     const CHAR_NUM: u8 = 48;
 }
 
+/// Get the display RAM column and row in pixels of the character cell at `column`, `row`.
+pub(crate) fn char_address(
+    size: &impl DisplaySize,
+    controller: Controller,
+    orientation: Orientation,
+    column: u8,
+    row: u8,
+) -> (u8, u8) {
+    let offset_x = column_offset(size, controller, orientation);
+
+    if orientation.is_transposed() {
+        (
+            offset_x.saturating_add(row * 8),
+            size.offset_y().saturating_add(column * 8),
+        )
+    } else {
+        (
+            offset_x.saturating_add(column * 8),
+            size.offset_y().saturating_add(row * 8),
+        )
+    }
+}
+
 /// Contains the new row that the cursor has wrapped around to
-struct CursorWrapEvent(u8);
+pub(crate) struct CursorWrapEvent(pub(crate) u8);
 
 #[derive(Copy, Clone, Debug)]
-struct Cursor {
+pub(crate) struct Cursor {
     col: u8,
     row: u8,
     width: u8,
@@ -56,9 +89,10 @@ impl Cursor {
     /// Returns a value indicating if this caused the cursor to wrap to the next line or the next
     /// screen.
     pub fn advance(&mut self) -> Option<CursorWrapEvent> {
-        self.col = (self.col + 1) % self.width;
+        // Displays smaller than a character have no cells, so stay at the origin
+        self.col = (self.col + 1).checked_rem(self.width).unwrap_or(0);
         if self.col == 0 {
-            self.row = (self.row + 1) % self.height;
+            self.row = (self.row + 1).checked_rem(self.height).unwrap_or(0);
             Some(CursorWrapEvent(self.row))
         } else {
             None
@@ -68,7 +102,7 @@ impl Cursor {
     /// Advances the logical cursor to the start of the next line
     /// Returns a value indicating the now active line
     pub fn advance_line(&mut self) -> CursorWrapEvent {
-        self.row = (self.row + 1) % self.height;
+        self.row = (self.row + 1).checked_rem(self.height).unwrap_or(0);
         self.col = 0;
         CursorWrapEvent(self.row)
     }
@@ -76,8 +110,8 @@ impl Cursor {
     /// Sets the position of the logical cursor arbitrarily.
     /// The position will be capped at the maximal possible position.
     pub fn set_position(&mut self, col: u8, row: u8) {
-        self.col = min(col, self.width - 1);
-        self.row = min(row, self.height - 1);
+        self.col = min(col, self.width.saturating_sub(1));
+        self.row = min(row, self.height.saturating_sub(1));
     }
 
     /// Gets the position of the logical cursor on screen in (col, row) order
@@ -97,6 +131,9 @@ pub enum TerminalModeError {
     /// An error occurred in the underlying interface layer
     InterfaceError(DisplayError),
     /// The mode was used before it was initialized
+    ///
+    /// This is no longer returned, as terminal mode can only be used once the display has been
+    /// initialized.
     Uninitialized,
     /// A location was specified outside the bounds of the screen
     OutOfBounds,
@@ -118,6 +155,16 @@ impl From<DisplayError> for TerminalModeError {
     }
 }
 
+impl From<Error> for TerminalModeError {
+    fn from(value: Error) -> Self {
+        match value {
+            Error::Comm(never) | Error::Pin(never) => match never {},
+            Error::Interface(error) => TerminalModeError::InterfaceError(error),
+            Error::InvalidArea | Error::OutOfBounds => TerminalModeError::OutOfBounds,
+        }
+    }
+}
+
 /// Terminal mode.
 #[derive(Copy, Clone, Debug)]
 pub struct TerminalMode {
@@ -134,7 +181,7 @@ impl TerminalMode {
 impl<DI, SIZE> DisplayConfig for Ssd1306<DI, SIZE, TerminalMode>
 where
     DI: WriteOnlyDataCommand,
-    SIZE: TerminalDisplaySize,
+    SIZE: DisplaySize,
 {
     type Error = TerminalModeError;
 
@@ -147,6 +194,14 @@ where
         self.reset_pos()
     }
 
+    /// Set the display orientation
+    ///
+    /// This method resets the cursor but does not clear the screen.
+    fn set_orientation(&mut self, orientation: Orientation) -> Result<(), TerminalModeError> {
+        self.set_orientation(orientation)?;
+        self.reset_pos()
+    }
+
     /// Initialise the display in page mode (i.e. a byte walks down a column of 8 pixels) with
     /// column 0 on the left and column _(SIZE::Width::U8 - 1)_ on the right, but no automatic line
     /// wrapping.
@@ -157,36 +212,45 @@ where
     }
 }
 
+impl<DI, SIZE> Ssd1306<DI, SIZE, TerminalMode>
+where
+    SIZE: DisplaySize,
+{
+    /// Commands that move the RAM pointer to the character cell at `column`, `row`. See
+    /// [`set_position`](Ssd1306::set_position).
+    pub(crate) fn position_commands(
+        &self,
+        column: u8,
+        row: u8,
+    ) -> Result<CommandBatch<1>, TerminalModeError> {
+        let (width, height) = self.mode.dimensions()?;
+        if column >= width || row >= height {
+            return Err(TerminalModeError::OutOfBounds);
+        }
+
+        let (ram_column, ram_row) =
+            char_address(&self.size, self.controller, self.orientation(), column, row);
+        if ram_row >= SIZE::DRIVER_ROWS {
+            return Err(TerminalModeError::OutOfBounds);
+        }
+
+        Ok(self.checked_commands(&[
+            Command::ColStart(ram_column),
+            Command::PageStart(Page::try_from(ram_row)?),
+        ])?)
+    }
+}
+
 impl<DI, SIZE> Ssd1306<DI, SIZE, TerminalMode>
 where
     DI: WriteOnlyDataCommand,
-    SIZE: TerminalDisplaySize,
+    SIZE: DisplaySize,
 {
     /// Clear the display and reset the cursor to the top left corner
     pub fn clear(&mut self) -> Result<(), TerminalModeError> {
-        // Let the chip handle line wrapping so we can fill the screen with blanks faster
-        self.set_addr_mode(AddrMode::Horizontal)?;
-
-        let offset_x = match self.rotation() {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
-            DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
-                // If segment remapping is flipped, we need to calculate
-                // the offset from the other edge of the display.
-                SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
-            }
-        };
-        self.set_draw_area(
-            (offset_x, SIZE::OFFSETY),
-            (SIZE::WIDTH + offset_x, SIZE::HEIGHT + SIZE::OFFSETY),
-        )?;
-
-        // Clear the display
-        for _ in 0..SIZE::CHAR_NUM {
-            self.draw(&[0; 8])?;
-        }
+        let (start, end) = self.panel_area();
+        self.clear_area(start, end)?;
 
-        // But for normal operation we manage the line wrapping
-        self.set_addr_mode(AddrMode::Page)?;
         self.reset_pos()?;
 
         Ok(())
@@ -205,15 +269,7 @@ where
                 self.ensure_cursor()?.set_position(0, cur_line);
             }
             _ => {
-                let bitmap = match self.rotation {
-                    DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                        Self::char_to_bitmap(c)
-                    }
-                    DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                        let bitmap = Self::char_to_bitmap(c);
-                        Self::rotate_bitmap(bitmap)
-                    }
-                };
+                let bitmap = TerminalMode::glyph(c, self.orientation);
 
                 self.draw(&bitmap)?;
 
@@ -228,52 +284,25 @@ where
     /// Get the current cursor position, in character coordinates.
     /// This is the (column, row) that the next character will be written to.
     pub fn position(&self) -> Result<(u8, u8), TerminalModeError> {
-        self.mode
-            .cursor
-            .as_ref()
-            .map(|c| c.get_position())
-            .ok_or(TerminalModeError::Uninitialized)
+        self.mode.position()
     }
 
     /// Set the cursor position, in character coordinates.
     /// This is the (column, row) that the next character will be written to.
     /// If the position is out of bounds, an Err will be returned.
     pub fn set_position(&mut self, column: u8, row: u8) -> Result<(), TerminalModeError> {
-        let (width, height) = self.ensure_cursor()?.get_dimensions();
-        if column >= width || row >= height {
-            Err(TerminalModeError::OutOfBounds)
-        } else {
-            let offset_x = match self.rotation() {
-                DisplayRotation::Rotate0 | DisplayRotation::Rotate270 => SIZE::OFFSETX,
-                DisplayRotation::Rotate180 | DisplayRotation::Rotate90 => {
-                    // If segment remapping is flipped, we need to calculate
-                    // the offset from the other edge of the display.
-                    SIZE::DRIVER_COLS - SIZE::WIDTH - SIZE::OFFSETX
-                }
-            };
-            match self.rotation() {
-                DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => {
-                    self.set_column(offset_x + column * 8)?;
-                    self.set_row(SIZE::OFFSETY + row * 8)?;
-                }
-                DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => {
-                    self.set_column(offset_x + row * 8)?;
-                    self.set_row(SIZE::OFFSETY + column * 8)?;
-                }
-            }
-            self.ensure_cursor()?.set_position(column, row);
-            Ok(())
-        }
+        self.position_commands(column, row)?
+            .send(&mut self.interface)?;
+        self.ensure_cursor()?.set_position(column, row);
+
+        Ok(())
     }
 
     /// Reset the draw area and move pointer to the top left corner
     fn reset_pos(&mut self) -> Result<(), TerminalModeError> {
         // Initialise the counter when we know it's valid
-        let (w, h) = match self.rotation() {
-            DisplayRotation::Rotate0 | DisplayRotation::Rotate180 => (SIZE::WIDTH, SIZE::HEIGHT),
-            DisplayRotation::Rotate90 | DisplayRotation::Rotate270 => (SIZE::HEIGHT, SIZE::WIDTH),
-        };
-        self.mode.cursor = Some(Cursor::new(w, h));
+        let dimensions = self.dimensions();
+        self.mode.reset_cursor(dimensions);
 
         // Reset cursor position
         self.set_position(0, 0)?;
@@ -294,12 +323,43 @@ where
     }
 
     fn ensure_cursor(&mut self) -> Result<&mut Cursor, TerminalModeError> {
-        self.mode
-            .cursor
-            .as_mut()
+        self.mode.ensure_cursor()
+    }
+}
+
+impl TerminalMode {
+    pub(crate) fn position(&self) -> Result<(u8, u8), TerminalModeError> {
+        self.cursor
+            .as_ref()
+            .map(|c| c.get_position())
             .ok_or(TerminalModeError::Uninitialized)
     }
 
+    pub(crate) fn dimensions(&self) -> Result<(u8, u8), TerminalModeError> {
+        self.cursor
+            .as_ref()
+            .map(|c| c.get_dimensions())
+            .ok_or(TerminalModeError::Uninitialized)
+    }
+
+    pub(crate) fn ensure_cursor(&mut self) -> Result<&mut Cursor, TerminalModeError> {
+        self.cursor.as_mut().ok_or(TerminalModeError::Uninitialized)
+    }
+
+    /// Reset the cursor for a display of the given size in pixels.
+    pub(crate) fn reset_cursor(&mut self, (width, height): (u8, u8)) {
+        self.cursor = Some(Cursor::new(width, height));
+    }
+
+    /// Get the bitmap to send to the display for a character, taking orientation into account.
+    pub(crate) fn glyph(c: char, orientation: Orientation) -> [u8; 8] {
+        if orientation.is_transposed() {
+            Self::rotate_bitmap(Self::char_to_bitmap(c))
+        } else {
+            Self::char_to_bitmap(c)
+        }
+    }
+
     fn char_to_bitmap(input: char) -> [u8; 8] {
         const CHARS: [[u8; 6]; 95] = [
             // !
@@ -523,7 +583,7 @@ where
 impl<DI, SIZE> core::fmt::Write for Ssd1306<DI, SIZE, TerminalMode>
 where
     DI: WriteOnlyDataCommand,
-    SIZE: TerminalDisplaySize,
+    SIZE: DisplaySize,
 {
     fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
         s.chars().map(move |c| self.print_char(c)).last();
@@ -0,0 +1,20 @@
+//! Display power state.
+
+/// Power state of the display, as last set by the driver.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum PowerState {
+    /// The display is on
+    On,
+    /// The display is off, but the charge pump is still running if it was enabled. Set by
+    /// [`set_display_on`](crate::Ssd1306::set_display_on) and before the display is initialised.
+    #[default]
+    Off,
+    /// The display and its charge pump are off. Set by [`sleep`](crate::Ssd1306::sleep).
+    ///
+    /// Buffered graphics modes keep drawing to the framebuffer while asleep, but don't send
+    /// anything until the display is woken up.
+    Asleep,
+}
+
+/// Time the display needs after being turned on before it is stable, in milliseconds.
+pub(crate) const WAKE_DELAY_MS: u32 = 100;
@@ -1,7 +1,7 @@
 //! Display rotation.
 
 /// Display rotation.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum DisplayRotation {
     /// No rotation, normal display
     Rotate0,
@@ -12,3 +12,104 @@ pub enum DisplayRotation {
     /// Rotate 270 degrees clockwise
     Rotate270,
 }
+
+/// Display orientation: one of the four rotations, optionally mirrored.
+///
+/// Mirrored orientations flip the rotated image left to right, for panels that are viewed in a
+/// mirror. Together with the rotations they cover every way a panel can be mounted, for example
+/// [`FlipVertical`](Orientation::FlipVertical) for a panel mounted upside down behind a mirror.
+///
+/// ```rust
+/// use ssd1306::rotation::{DisplayRotation, Orientation};
+///
+/// let orientation = Orientation::new(DisplayRotation::Rotate180, true);
+/// assert_eq!(orientation, Orientation::FlipVertical);
+/// assert_eq!(orientation.rotation(), DisplayRotation::Rotate180);
+/// assert!(orientation.is_mirrored());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    /// No rotation, normal display
+    Rotate0,
+    /// Rotate by 90 degrees clockwise
+    Rotate90,
+    /// Rotate by 180 degrees clockwise
+    Rotate180,
+    /// Rotate by 270 degrees clockwise
+    Rotate270,
+    /// Mirror left to right
+    FlipHorizontal,
+    /// Mirror top to bottom, the same as rotating by 180 degrees and mirroring left to right
+    FlipVertical,
+    /// Mirror along the diagonal from the top left corner, the same as rotating by 270 degrees
+    /// and mirroring left to right
+    Transpose,
+    /// Mirror along the diagonal from the top right corner, the same as rotating by 90 degrees
+    /// and mirroring left to right
+    Transverse,
+}
+
+impl Orientation {
+    /// Rotate by `rotation`, then mirror left to right if `mirrored` is set.
+    pub const fn new(rotation: DisplayRotation, mirrored: bool) -> Self {
+        match (rotation, mirrored) {
+            (DisplayRotation::Rotate0, false) => Orientation::Rotate0,
+            (DisplayRotation::Rotate90, false) => Orientation::Rotate90,
+            (DisplayRotation::Rotate180, false) => Orientation::Rotate180,
+            (DisplayRotation::Rotate270, false) => Orientation::Rotate270,
+            (DisplayRotation::Rotate0, true) => Orientation::FlipHorizontal,
+            (DisplayRotation::Rotate90, true) => Orientation::Transverse,
+            (DisplayRotation::Rotate180, true) => Orientation::FlipVertical,
+            (DisplayRotation::Rotate270, true) => Orientation::Transpose,
+        }
+    }
+
+    /// The rotation applied before mirroring.
+    pub const fn rotation(self) -> DisplayRotation {
+        match self {
+            Orientation::Rotate0 | Orientation::FlipHorizontal => DisplayRotation::Rotate0,
+            Orientation::Rotate90 | Orientation::Transverse => DisplayRotation::Rotate90,
+            Orientation::Rotate180 | Orientation::FlipVertical => DisplayRotation::Rotate180,
+            Orientation::Rotate270 | Orientation::Transpose => DisplayRotation::Rotate270,
+        }
+    }
+
+    /// Whether the rotated image is mirrored left to right.
+    pub const fn is_mirrored(self) -> bool {
+        matches!(
+            self,
+            Orientation::FlipHorizontal
+                | Orientation::FlipVertical
+                | Orientation::Transpose
+                | Orientation::Transverse
+        )
+    }
+
+    /// Whether X and Y are swapped, i.e. the display is rotated by 90 or 270 degrees. The buffer
+    /// is laid out with columns along Y then.
+    pub(crate) const fn is_transposed(self) -> bool {
+        matches!(
+            self.rotation(),
+            DisplayRotation::Rotate90 | DisplayRotation::Rotate270
+        )
+    }
+
+    /// Segment remap and reverse COM scan direction settings that produce this orientation.
+    ///
+    /// The hardware can only flip the panel horizontally and vertically. Swapping X and Y for 90
+    /// and 270 degree rotations is done when drawing into the buffer.
+    pub(crate) const fn remap_and_com_dir(self) -> (bool, bool) {
+        match self {
+            Orientation::Rotate0 | Orientation::Transpose => (true, true),
+            Orientation::Rotate90 | Orientation::FlipHorizontal => (false, true),
+            Orientation::Rotate180 | Orientation::Transverse => (false, false),
+            Orientation::Rotate270 | Orientation::FlipVertical => (true, false),
+        }
+    }
+}
+
+impl From<DisplayRotation> for Orientation {
+    fn from(rotation: DisplayRotation) -> Self {
+        Orientation::new(rotation, false)
+    }
+}
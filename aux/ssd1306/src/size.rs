@@ -1,6 +1,6 @@
 //! Display size.
 
-use super::command::Command;
+use super::{command::Command, controller::Controller, rotation::Orientation};
 use display_interface::{DisplayError, WriteOnlyDataCommand};
 
 /// Workaround trait, since `Default` is only implemented to arrays up to 32 of size
@@ -27,6 +27,10 @@ pub trait DisplaySize {
     const HEIGHT: u8;
 
     /// Maximum width supported by the display driver
+    #[deprecated(
+        since = "0.9.0",
+        note = "the number of columns depends on the controller, see `Controller::ram_columns`"
+    )]
     const DRIVER_COLS: u8 = 128;
 
     /// Maximum height supported by the display driver
@@ -40,14 +44,83 @@ pub trait DisplaySize {
 
     /// Size of framebuffer. Because the display is monochrome, this is
     /// width * height / 8
-    type Buffer: AsMut<[u8]> + NewZeroed;
+    type Buffer: AsRef<[u8]> + AsMut<[u8]> + NewZeroed;
+
+    /// Width in pixels of this display. Defaults to [`WIDTH`](Self::WIDTH), sizes chosen at
+    /// runtime return their own value.
+    fn width(&self) -> u8 {
+        Self::WIDTH
+    }
+
+    /// Height in pixels of this display. Defaults to [`HEIGHT`](Self::HEIGHT).
+    fn height(&self) -> u8 {
+        Self::HEIGHT
+    }
+
+    /// Horizontal offset in pixels of this display. Defaults to [`OFFSETX`](Self::OFFSETX).
+    fn offset_x(&self) -> u8 {
+        Self::OFFSETX
+    }
+
+    /// Vertical offset in pixels of this display. Defaults to [`OFFSETY`](Self::OFFSETY).
+    fn offset_y(&self) -> u8 {
+        Self::OFFSETY
+    }
+
+    /// Number of rows the controller drives, sent as the multiplex ratio during initialisation.
+    /// Defaults to the [`height`](Self::height).
+    fn multiplex(&self) -> u8 {
+        self.height()
+    }
+
+    /// Model-dependent configuration commands, if they are known at compile time. Sizes that are
+    /// configured at runtime leave this as `None` and override [`configure`](Self::configure).
+    ///
+    /// Used by [`Ssd1306Config::init_sequence`](crate::Ssd1306Config::init_sequence).
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> = None;
 
     /// Send resolution and model-dependent configuration to the display
     ///
-    /// See [`Command::ComPinConfig`]()
-    /// and [`Command::InternalIref`]()
-    /// for more information
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError>;
+    /// By default this sends [`CONFIGURE_COMMANDS`](Self::CONFIGURE_COMMANDS). See
+    /// [`Command::ComPinConfig`]() and [`Command::InternalIref`]() for more information
+    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
+        Self::CONFIGURE_COMMANDS
+            .unwrap_or(&[])
+            .iter()
+            .try_for_each(|command| command.send(iface))
+    }
+}
+
+/// Get the display dimensions for the given orientation.
+pub(crate) fn dimensions(size: &impl DisplaySize, orientation: Orientation) -> (u8, u8) {
+    if orientation.is_transposed() {
+        (size.height(), size.width())
+    } else {
+        (size.width(), size.height())
+    }
+}
+
+/// Get the column in display RAM of the first visible pixel for the given controller and
+/// orientation.
+pub(crate) fn column_offset(
+    size: &impl DisplaySize,
+    controller: Controller,
+    orientation: Orientation,
+) -> u8 {
+    // Saturate for sizes that don't fit in display RAM, which `init` rejects
+    let offset = controller.column_offset().saturating_add(size.offset_x());
+
+    let (remap, _) = orientation.remap_and_com_dir();
+    if remap {
+        offset
+    } else {
+        // If segment remapping is flipped, we need to calculate
+        // the offset from the other edge of the display.
+        controller
+            .ram_columns()
+            .saturating_sub(size.width())
+            .saturating_sub(offset)
+    }
 }
 
 /// Size information for the common 128x64 variants
@@ -58,9 +131,8 @@ impl DisplaySize for DisplaySize128x64 {
     const HEIGHT: u8 = 64;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(true, false).send(iface)
-    }
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> =
+        Some(&[Command::ComPinConfig(true, false)]);
 }
 
 /// Size information for the common 128x32 variants
@@ -71,9 +143,8 @@ impl DisplaySize for DisplaySize128x32 {
     const HEIGHT: u8 = 32;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(false, false).send(iface)
-    }
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> =
+        Some(&[Command::ComPinConfig(false, false)]);
 }
 
 /// Size information for the common 96x16 variants
@@ -84,9 +155,8 @@ impl DisplaySize for DisplaySize96x16 {
     const HEIGHT: u8 = 16;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(false, false).send(iface)
-    }
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> =
+        Some(&[Command::ComPinConfig(false, false)]);
 }
 
 /// Size information for the common 72x40 variants
@@ -99,10 +169,10 @@ impl DisplaySize for DisplaySize72x40 {
     const OFFSETY: u8 = 0;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(true, false).send(iface)?;
-        Command::InternalIref(true, true).send(iface)
-    }
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> = Some(&[
+        Command::ComPinConfig(true, false),
+        Command::InternalIref(true, true),
+    ]);
 }
 
 /// Size information for the common 64x48 variants
@@ -115,9 +185,8 @@ impl DisplaySize for DisplaySize64x48 {
     const OFFSETY: u8 = 0;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
-    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(true, false).send(iface)
-    }
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> =
+        Some(&[Command::ComPinConfig(true, false)]);
 }
 
 /// Size information for the common 64x32 variants
@@ -130,7 +199,288 @@ impl DisplaySize for DisplaySize64x32 {
     const OFFSETY: u8 = 0;
     type Buffer = [u8; Self::WIDTH as usize * Self::HEIGHT as usize / 8];
 
+    const CONFIGURE_COMMANDS: Option<&'static [Command]> =
+        Some(&[Command::ComPinConfig(true, false)]);
+}
+
+/// Widest panel any controller can drive. The SH1106 has 132 columns of display RAM and starts
+/// addressing the panel at column 2, which leaves 130. The other controllers leave 128.
+const MAX_PANEL_COLUMNS: u8 = 130;
+
+/// Size of the framebuffer used by [`DisplaySizeDynamic`], enough for the widest panel with all
+/// 64 rows.
+const MAX_BUFFER_SIZE: usize = buffer_size(MAX_PANEL_COLUMNS, 64);
+
+/// Size in bytes of the framebuffer of a `width` x `height` panel, `width * ceil(height / 8)`.
+///
+/// Use this to give [`DisplaySizeCustom`] its buffer size.
+pub const fn buffer_size(width: u8, height: u8) -> usize {
+    width as usize * (height as usize).div_ceil(8)
+}
+
+/// Size information for panels without a predefined size.
+///
+/// `W` x `H` is the panel size in pixels and `OX`, `OY` its offset in display RAM. The COM pin
+/// configuration and multiplex ratio can be changed with the `with_*` methods.
+///
+/// The framebuffer size is given as `N`, which must be [`buffer_size(W, H)`](buffer_size), or
+/// `W * ceil(H / 8)` bytes. Stable Rust can't use an expression of `W` and `H` as the length of
+/// the buffer array, that needs the unstable `generic_const_exprs` feature, so it can't be
+/// derived inside the type. Other buffer sizes, empty panels and panels taller than the 64 rows
+/// of display RAM fail to compile when the size is created.
+///
+/// ```rust
+/// use ssd1306::{emulator::Emulator, prelude::*, size::buffer_size, Ssd1306};
+///
+/// // An 88x48 panel, centered in the 128 columns of display RAM
+/// type DisplaySize88x48 = DisplaySizeCustom<88, 48, { buffer_size(88, 48) }, 20>;
+///
+/// let mut emulator = Emulator::new(DisplaySize88x48::new());
+///
+/// let mut display = Ssd1306::new(&mut emulator, DisplaySize88x48::new(), DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode()
+///     .init()
+///     .unwrap();
+/// display.set_pixel(87, 47, true);
+/// display.flush().unwrap();
+/// drop(display);
+///
+/// assert!(emulator.panel_pixel(87, 47));
+/// ```
+///
+/// A buffer size that doesn't match the panel is a compile error:
+///
+/// ```rust,compile_fail
+/// use ssd1306::prelude::*;
+///
+/// // 128x64 needs 1024 bytes
+/// let size = DisplaySizeCustom::<128, 64, 512>::new();
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct DisplaySizeCustom<
+    const W: u8,
+    const H: u8,
+    const N: usize,
+    const OX: u8 = 0,
+    const OY: u8 = 0,
+> {
+    com_pins: (bool, bool),
+    multiplex: u8,
+}
+
+impl<const W: u8, const H: u8, const N: usize, const OX: u8, const OY: u8>
+    DisplaySizeCustom<W, H, N, OX, OY>
+{
+    const VALID: () = {
+        assert!(W > 0 && H > 0, "Display size is empty");
+        assert!(H <= 64, "Display RAM has 64 rows");
+        assert!(N == buffer_size(W, H), "N must be buffer_size(W, H)");
+    };
+
+    /// Create a custom size with alternative COM pin configuration without left/right remap, as
+    /// used by most panels, driving `H` rows.
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+
+        Self {
+            com_pins: (true, false),
+            multiplex: H,
+        }
+    }
+
+    /// Set the COM pin configuration. See [`Command::ComPinConfig`].
+    pub const fn with_com_pins(self, alternative: bool, remap: bool) -> Self {
+        Self {
+            com_pins: (alternative, remap),
+            ..self
+        }
+    }
+
+    /// Set the number of rows driven, for panels whose COM lines don't match their height.
+    ///
+    /// Initialisation fails with [`DisplayError::OutOfBoundsError`] unless this is 1 to 64.
+    ///
+    /// ```rust
+    /// use ssd1306::{
+    ///     command::Command, prelude::*, size::buffer_size, test_helpers::CommandLog, Ssd1306,
+    /// };
+    ///
+    /// // A 128x32 panel wired to all 64 COM lines
+    /// let size = DisplaySizeCustom::<128, 32, { buffer_size(128, 32) }>::new().with_multiplex(64);
+    ///
+    /// let mut log = CommandLog::new();
+    /// let display = Ssd1306::new(&mut log, size, DisplayRotation::Rotate0).init().unwrap();
+    /// drop(display);
+    ///
+    /// let mut multiplex = Command::decode_all(log.commands())
+    ///     .filter(|command| matches!(command, Ok(Command::Multiplex(_))));
+    /// assert_eq!(multiplex.next(), Some(Ok(Command::Multiplex(63))));
+    /// assert_eq!(multiplex.next(), None);
+    /// ```
+    pub const fn with_multiplex(self, rows: u8) -> Self {
+        Self {
+            multiplex: rows,
+            ..self
+        }
+    }
+}
+
+impl<const W: u8, const H: u8, const N: usize, const OX: u8, const OY: u8> Default
+    for DisplaySizeCustom<W, H, N, OX, OY>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: u8, const H: u8, const N: usize, const OX: u8, const OY: u8> DisplaySize
+    for DisplaySizeCustom<W, H, N, OX, OY>
+{
+    const WIDTH: u8 = W;
+    const HEIGHT: u8 = H;
+    const OFFSETX: u8 = OX;
+    const OFFSETY: u8 = OY;
+    type Buffer = [u8; N];
+
+    fn multiplex(&self) -> u8 {
+        self.multiplex
+    }
+
+    fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
+        let (alternative, remap) = self.com_pins;
+        Command::ComPinConfig(alternative, remap).send(iface)
+    }
+}
+
+/// Size information chosen at runtime, for firmware that supports several panels with one build.
+///
+/// The width, height and offsets are checked when the size is created. Panels can be up to 130
+/// columns wide including their offset, the most the SH1106 can address after its column
+/// offset, and 64 rows high. Controllers that leave fewer columns check the size again when
+/// the display is initialised. 132 column panels can't be driven: the SH1106 and SSD1305 have
+/// 132 columns of display RAM, but the panel is addressed from their column offset.
+///
+/// The framebuffer is always 1040 bytes, enough for any size that passes. The associated
+/// constants describe that maximum, the [`width`](DisplaySize::width),
+/// [`height`](DisplaySize::height) and offset methods return the actual values.
+///
+/// ```rust
+/// use ssd1306::{emulator::Emulator, prelude::*, size::DisplaySizeDynamic, Ssd1306};
+///
+/// # let strap = 2;
+/// let size = match strap {
+///     0 => DisplaySizeDynamic::new(128, 64),
+///     1 => DisplaySizeDynamic::new(128, 32).map(|size| size.with_com_pins(false, false)),
+///     _ => DisplaySizeDynamic::new(64, 48).and_then(|size| size.with_offset(32, 0)),
+/// }
+/// .unwrap();
+///
+/// let mut emulator = Emulator::new(size);
+///
+/// let mut display = Ssd1306::new(&mut emulator, size, DisplayRotation::Rotate0)
+///     .into_buffered_graphics_mode()
+///     .init()
+///     .unwrap();
+/// assert_eq!(display.dimensions(), (64, 48));
+///
+/// display.set_pixel(63, 47, true);
+/// display.flush().unwrap();
+/// drop(display);
+///
+/// assert!(emulator.panel_pixel(63, 47));
+///
+/// // Sizes and offsets past display RAM are rejected straight away
+/// assert!(DisplaySizeDynamic::new(130, 64).is_ok());
+/// assert!(DisplaySizeDynamic::new(132, 64).is_err());
+/// assert!(DisplaySizeDynamic::new(128, 32).and_then(|size| size.with_offset(0, 33)).is_err());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DisplaySizeDynamic {
+    width: u8,
+    height: u8,
+    offset_x: u8,
+    offset_y: u8,
+    com_pins: (bool, bool),
+}
+
+impl DisplaySizeDynamic {
+    /// Create a size of `width` x `height` pixels with alternative COM pin configuration without
+    /// left/right remap, as used by most panels.
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the size is empty or larger than 130 x 64
+    /// pixels.
+    pub const fn new(width: u8, height: u8) -> Result<Self, DisplayError> {
+        Self {
+            width,
+            height,
+            offset_x: 0,
+            offset_y: 0,
+            com_pins: (true, false),
+        }
+        .checked()
+    }
+
+    /// Set the offset of the panel in display RAM.
+    ///
+    /// Returns [`DisplayError::OutOfBoundsError`] if the panel and its offset no longer fit in
+    /// 130 x 64 pixels.
+    pub const fn with_offset(self, offset_x: u8, offset_y: u8) -> Result<Self, DisplayError> {
+        Self {
+            offset_x,
+            offset_y,
+            ..self
+        }
+        .checked()
+    }
+
+    /// Check that the panel isn't empty and some controller can drive it.
+    const fn checked(self) -> Result<Self, DisplayError> {
+        let columns = self.width as u16 + self.offset_x as u16;
+        let rows = self.height as u16 + self.offset_y as u16;
+
+        if self.width == 0
+            || self.height == 0
+            || columns > MAX_PANEL_COLUMNS as u16
+            || rows > <Self as DisplaySize>::DRIVER_ROWS as u16
+        {
+            Err(DisplayError::OutOfBoundsError)
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Set the COM pin configuration. See [`Command::ComPinConfig`].
+    pub const fn with_com_pins(self, alternative: bool, remap: bool) -> Self {
+        Self {
+            com_pins: (alternative, remap),
+            ..self
+        }
+    }
+}
+
+impl DisplaySize for DisplaySizeDynamic {
+    const WIDTH: u8 = MAX_PANEL_COLUMNS;
+    const HEIGHT: u8 = 64;
+    type Buffer = [u8; MAX_BUFFER_SIZE];
+
+    fn width(&self) -> u8 {
+        self.width
+    }
+
+    fn height(&self) -> u8 {
+        self.height
+    }
+
+    fn offset_x(&self) -> u8 {
+        self.offset_x
+    }
+
+    fn offset_y(&self) -> u8 {
+        self.offset_y
+    }
+
     fn configure(&self, iface: &mut impl WriteOnlyDataCommand) -> Result<(), DisplayError> {
-        Command::ComPinConfig(true, false).send(iface)
+        let (alternative, remap) = self.com_pins;
+        Command::ComPinConfig(alternative, remap).send(iface)
     }
 }
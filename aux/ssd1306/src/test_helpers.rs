@@ -1,31 +1,25 @@
 //! Helpers for use in examples and tests
 
 use display_interface::{DisplayError, WriteOnlyDataCommand};
+use core::convert::Infallible;
 use embedded_hal::{
-    blocking::{
-        i2c,
-        spi::{self, Transfer},
-    },
-    digital::v2::OutputPin,
+    delay::DelayNs,
+    digital::{self, OutputPin},
+    i2c::{self, I2c},
+    spi::{self, SpiDevice},
 };
 
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy)]
 pub struct SpiStub;
 
-impl spi::Write<u8> for SpiStub {
-    type Error = ();
-
-    fn write(&mut self, _buf: &[u8]) -> Result<(), ()> {
-        Ok(())
-    }
+impl spi::ErrorType for SpiStub {
+    type Error = Infallible;
 }
 
-impl Transfer<u8> for SpiStub {
-    type Error = ();
-
-    fn transfer<'a>(&mut self, buf: &'a mut [u8]) -> Result<&'a [u8], ()> {
-        Ok(buf)
+impl SpiDevice for SpiStub {
+    fn transaction(&mut self, _operations: &mut [spi::Operation<'_, u8>]) -> Result<(), Infallible> {
+        Ok(())
     }
 }
 
@@ -33,10 +27,16 @@ impl Transfer<u8> for SpiStub {
 #[derive(Debug, Clone, Copy)]
 pub struct I2cStub;
 
-impl i2c::Write for I2cStub {
-    type Error = ();
+impl i2c::ErrorType for I2cStub {
+    type Error = Infallible;
+}
 
-    fn write(&mut self, _addr: u8, _buf: &[u8]) -> Result<(), ()> {
+impl I2c for I2cStub {
+    fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [i2c::Operation<'_>],
+    ) -> Result<(), Infallible> {
         Ok(())
     }
 }
@@ -45,18 +45,33 @@ impl i2c::Write for I2cStub {
 #[derive(Debug, Clone, Copy)]
 pub struct PinStub;
 
-impl OutputPin for PinStub {
-    type Error = ();
+impl digital::ErrorType for PinStub {
+    type Error = Infallible;
+}
 
-    fn set_high(&mut self) -> Result<(), ()> {
+impl OutputPin for PinStub {
+    fn set_high(&mut self) -> Result<(), Infallible> {
         Ok(())
     }
 
-    fn set_low(&mut self) -> Result<(), ()> {
+    fn set_low(&mut self) -> Result<(), Infallible> {
         Ok(())
     }
 }
 
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct DelayStub;
+
+impl DelayNs for DelayStub {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::delay::DelayNs for DelayStub {
+    async fn delay_ns(&mut self, _ns: u32) {}
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone, Copy)]
 pub struct StubInterface;
@@ -72,3 +87,150 @@ impl WriteOnlyDataCommand for StubInterface {
         Ok(())
     }
 }
+
+#[cfg(feature = "async")]
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct AsyncI2cStub;
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::ErrorType for AsyncI2cStub {
+    type Error = Infallible;
+}
+
+#[cfg(feature = "async")]
+impl embedded_hal_async::i2c::I2c for AsyncI2cStub {
+    async fn transaction(
+        &mut self,
+        _address: u8,
+        _operations: &mut [embedded_hal_async::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy)]
+pub struct I2cStub02;
+
+#[cfg(feature = "embedded-hal-02")]
+impl embedded_hal_02::blocking::i2c::Write for I2cStub02 {
+    type Error = ();
+
+    fn write(&mut self, _addr: u8, _buf: &[u8]) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+/// Interface that records the command bytes sent to it, to compare against an expected sequence.
+///
+/// Data isn't kept, but its length and a checksum of it are, so two logs only compare equal if
+/// the same commands and data were sent in the same transactions.
+#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandLog {
+    bytes: [u8; 256],
+    len: usize,
+    data_len: usize,
+    data_hash: u32,
+    transactions: usize,
+}
+
+impl Default for CommandLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(dead_code)]
+impl CommandLog {
+    pub fn new() -> Self {
+        Self {
+            bytes: [0; 256],
+            len: 0,
+            data_len: 0,
+            // FNV-1a offset basis
+            data_hash: 0x811C_9DC5,
+            transactions: 0,
+        }
+    }
+
+    /// Command bytes sent so far.
+    pub fn commands(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Number of data bytes sent so far.
+    pub fn data_len(&self) -> usize {
+        self.data_len
+    }
+
+    /// Number of transactions, command or data, sent so far.
+    pub fn transactions(&self) -> usize {
+        self.transactions
+    }
+
+    fn record_commands(
+        &mut self,
+        cmd: display_interface::DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        match cmd {
+            display_interface::DataFormat::U8(bytes) => {
+                let end = self.len + bytes.len();
+                self.bytes
+                    .get_mut(self.len..end)
+                    .ok_or(DisplayError::OutOfBoundsError)?
+                    .copy_from_slice(bytes);
+                self.len = end;
+                self.transactions += 1;
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+
+    fn record_data(&mut self, buf: display_interface::DataFormat<'_>) -> Result<(), DisplayError> {
+        match buf {
+            display_interface::DataFormat::U8(bytes) => {
+                self.data_len += bytes.len();
+                self.data_hash = bytes.iter().fold(self.data_hash, |hash, &byte| {
+                    (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+                });
+                self.transactions += 1;
+
+                Ok(())
+            }
+            _ => Err(DisplayError::DataFormatNotImplemented),
+        }
+    }
+}
+
+impl WriteOnlyDataCommand for &mut CommandLog {
+    fn send_commands(
+        &mut self,
+        cmd: display_interface::DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        self.record_commands(cmd)
+    }
+    fn send_data(&mut self, buf: display_interface::DataFormat<'_>) -> Result<(), DisplayError> {
+        self.record_data(buf)
+    }
+}
+
+#[cfg(feature = "async")]
+impl display_interface::AsyncWriteOnlyDataCommand for &mut CommandLog {
+    async fn send_commands(
+        &mut self,
+        cmd: display_interface::DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        self.record_commands(cmd)
+    }
+    async fn send_data(
+        &mut self,
+        buf: display_interface::DataFormat<'_>,
+    ) -> Result<(), DisplayError> {
+        self.record_data(buf)
+    }
+}
@@ -12,7 +12,7 @@ use stm32f1xx_hal::{
 };
 use nb::block;
 
-use ssd1306::{prelude::*, Ssd1306,I2CDisplayInterface, size::DisplaySize64x48 };
+use ssd1306::{prelude::*, Ssd1306, Ssd1306Config, eh02::I2CDisplayInterface, size::DisplaySize64x48 };
 use ssd1306::mode::BufferedGraphicsMode;
 
 use embedded_graphics::{
@@ -74,11 +74,15 @@ fn main() -> ! {
     timer.start(6.Hz()).unwrap();
 
     let interface = I2CDisplayInterface::new_alternate_address(i2c);
+    // The Micro OLED needs the longest precharge phase 2 (0xD9 0xF1) and a contrast of 0x8F
+    let config = Ssd1306Config::new().with_brightness(Brightness::custom(0xF, 0x8F));
     let mut display = Ssd1306::new(interface, DisplaySize64x48, DisplayRotation::Rotate0)
-        .into_buffered_graphics_mode();
-    display.init().unwrap();
+        .with_config(config)
+        .into_buffered_graphics_mode()
+        .init()
+        .unwrap();
 
-    display.set_draw_area( (0x20u8, 0x00u8), ( 0x20u8 + 64, 0x06u8)).unwrap();
+    display.set_draw_area( (0x20u8, 0x00u8), ( 0x20u8 + 64, 48u8)).unwrap();
    
     const IMAGE_WIDTH : i32 = 48;
     const IMAGE_CENTER : i32 = IMAGE_WIDTH /2;
@@ -11,10 +11,9 @@ use stm32f1xx_hal::{
     prelude::*,
     stm32,
 };
-use nb::block;
 
-use ssd1306::{prelude::*, Ssd1306,I2CDisplayInterface, size::DisplaySize64x48 };
-use ssd1306::mode::BufferedGraphicsMode;
+use ssd1306::{prelude::*, Ssd1306, Ssd1306Config, eh02::I2CDisplayInterface, size::DisplaySize64x48 };
+use ssd1306::mode::{BufferedGraphicsMode, Shadowed};
 
 use embedded_graphics::{
     prelude::*,
@@ -37,7 +36,8 @@ const GAUGE_CENTER: (f32, f32) = (CENTER_X as f32, (HEIGHT as f32 * 0.75));
 const GAUGE_WIDTH: f32 = 3.0;
 const LINE_LENGTH: f32 = 20.0;
 
-type SparkFunDisplay<DI> = Ssd1306<DI, DisplaySize64x48, BufferedGraphicsMode<DisplaySize64x48>>;
+// Each frame is cleared and redrawn, so keep a shadow copy and only send what actually changed
+type SparkFunDisplay<DI> = Ssd1306<DI, DisplaySize64x48, BufferedGraphicsMode<DisplaySize64x48, Shadowed>>;
 #[entry]
 fn main() -> ! {
     let cp = cortex_m::Peripherals::take().unwrap();
@@ -79,19 +79,32 @@ fn main() -> ! {
     timer.start(6.Hz()).unwrap();
 
     let interface = I2CDisplayInterface::new_alternate_address(i2c);
+    // The Micro OLED needs the longest precharge phase 2 (0xD9 0xF1) and a contrast of 0x8F
+    let config = Ssd1306Config::new().with_brightness(Brightness::custom(0xF, 0x8F));
     let mut display = Ssd1306::new(interface, DisplaySize64x48, DisplayRotation::Rotate0)
-        .into_buffered_graphics_mode();
-    display.init().unwrap();
+        .with_config(config)
+        .into_shadowed_graphics_mode()
+        .init()
+        .unwrap();
 
     // let test = (0x20, 0x00);
-    display.set_draw_area( (0x20u8, 0x00u8), ( 0x20u8 + 64, 0x06u8)).unwrap();
+    display.set_draw_area( (0x20u8, 0x00u8), ( 0x20u8 + 64, 48u8)).unwrap();
     // display.set_column(0).unwrap();
     // display.set_row(0).unwrap();
-    display.draw(rust_logo::IMAGE).unwrap();
+    display.draw(sparkfun_logo::IMAGE).unwrap();
 
     display.flush().unwrap();
 
 
+    // Toggle the LED on every timer tick, returning whether one happened
+    let mut tick = || {
+        let ticked = timer.wait().is_ok();
+        if ticked {
+            led.toggle();
+        }
+        ticked
+    };
+
     loop {
         for deg in 0..180 { 
             display.clear(BinaryColor::Off).unwrap();
@@ -99,21 +112,33 @@ fn main() -> ! {
             draw_dial(&mut display, deg as f32).unwrap();
             draw_dial_center(&mut display).unwrap();
 
-            display.flush().unwrap();
-
-            block!(timer.wait()).unwrap();
-            led.toggle();
+            show_frame(&mut display, &mut tick);
         }
         for deg in 180..0 { 
             display.clear(BinaryColor::Off).unwrap();
 
             draw_dial(&mut display, deg as f32).unwrap();
             draw_dial_center(&mut display).unwrap();
-            display.flush().unwrap();
 
-            block!(timer.wait()).unwrap();
-            led.toggle();
+            show_frame(&mut display, &mut tick);
+        }
+    }
+}
+
+// Send the frame a page per step, polling the timer (and toggling the LED) between steps, then
+// wait for the next tick. A tick that arrives while the frame is still being sent is remembered,
+// so the next frame isn't held back a whole period.
+fn show_frame<DI>(display: &mut SparkFunDisplay<DI>, tick: &mut impl FnMut() -> bool)
+where
+    DI: WriteOnlyDataCommand
+{
+    let mut flushed = false;
+    let mut ticked = false;
+    while !(flushed && ticked) {
+        if !flushed {
+            flushed = display.flush_job().step().unwrap();
         }
+        ticked |= tick();
     }
 }
 